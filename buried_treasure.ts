@@ -43,10 +43,17 @@ describe("buried_treasure", () => {
   let cipher1: any;
   let cipher2: any;
 
+  // Game PDAs are namespaced per authority by a caller-chosen id
+  const gameId = new anchor.BN(1);
+
   // PDA helpers
   function getGamePDA(): PublicKey {
     const [pda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("game"), authority.publicKey.toBuffer()],
+      [
+        Buffer.from("game"),
+        authority.publicKey.toBuffer(),
+        gameId.toArrayLike(Buffer, "le", 8),
+      ],
       programId
     );
     return pda;
@@ -126,18 +133,35 @@ describe("buried_treasure", () => {
     const computationOffset = BigInt(Date.now());
     const gamePDA = getGamePDA();
 
-    // Encrypt seed
-    const seedBuffer = Buffer.alloc(8);
-    seedBuffer.writeBigUInt64LE(BigInt(42)); // Deterministic seed for testing
-    const { ciphertext, nonce } = cipher1.encrypt(seedBuffer);
-    const nonceBN = new anchor.BN(Buffer.from(nonce));
-
+    // No seed to send — the cluster draws it inside init_map
     const tx = await program.methods
       .createGame(
         new anchor.BN(computationOffset.toString()),
-        Array.from(ciphertext[0]) as any,
-        Array.from(clientKeypair1.publicKey.toBytes()) as any,
-        nonceBN
+        gameId,
+        new anchor.BN(0), // no deadline
+        new anchor.BN(0), // no turn order
+        {
+          width: 10,
+          height: 10,
+          treasureCount: 15,
+          trapCount: 10,
+          treasureMin: 5,
+          treasureMax: 50,
+          trapMin: 5,
+          trapMax: 30,
+          levels: 1,
+          wallCount: 0,
+          springCount: 0,
+          portalPairs: 0,
+          itemCount: 0,
+        },
+        {
+          entryFeeLamports: new anchor.BN(0), // free to join
+          rakeBps: 0,
+          maxPlayers: 0, // unlimited
+          payoutCurve: { proportional: {} },
+        },
+        { maxActionPoints: 0, slotsPerPoint: new anchor.BN(0) } // no cooldown
       )
       .accountsPartial({
         game: gamePDA,
@@ -269,8 +293,10 @@ describe("buried_treasure", () => {
     // Decrypt result — only Player 1's cipher can do this
     if (moveResult) {
       const decrypted = cipher1.decrypt(moveResult.encryptedResult);
-      const newX = decrypted.readUInt8(0);
-      const newY = decrypted.readUInt8(1);
+      const status = decrypted.readUInt8(0);
+      const newX = decrypted.readUInt8(1);
+      const newY = decrypted.readUInt8(2);
+      expect(status).to.equal(0); // STATUS_OK
       expect(newX).to.equal(1);
       expect(newY).to.equal(0);
       console.log(`  ✓ Moved to (${newX}, ${newY}) — verified via MPC`);
@@ -325,10 +351,12 @@ describe("buried_treasure", () => {
     if (exploreResult) {
       // Only cipher1 can decrypt — cipher2 would get garbage
       const decrypted = cipher1.decrypt(exploreResult.encryptedResult);
-      const tileType = decrypted.readUInt8(0);
-      const value = decrypted.readUInt16LE(1);
+      const status = decrypted.readUInt8(0);
+      const tileType = decrypted.readUInt8(1);
+      const value = decrypted.readUInt16LE(2);
+      expect(status).to.equal(0); // STATUS_OK
 
-      const typeNames = ["empty", "treasure", "trap"];
+      const typeNames = ["empty", "treasure", "trap", "staircase", "wall", "spring", "portal", "cache"];
       console.log(`  ✓ Tile (2,0) revealed to Player 1: ${typeNames[tileType]}, value=${value}`);
       console.log("  ✓ This result is encrypted — Player 2 cannot decrypt it");
     }
@@ -390,9 +418,9 @@ describe("buried_treasure", () => {
       console.log("  ✓ No one can determine WHO buried WHAT WHERE");
 
       const decrypted = cipher1.decrypt(buryResult.encryptedResult);
-      const success = decrypted.readUInt8(0);
+      const status = decrypted.readUInt8(0);
       const newGold = decrypted.readUInt16LE(1);
-      console.log(`  ✓ Confirmed: success=${success}, remaining gold=${newGold}`);
+      console.log(`  ✓ Confirmed: status=${status}, remaining gold=${newGold}`);
     }
   });
 
@@ -0,0 +1,17 @@
+#!/bin/bash
+
+# Fetch the Arcium program for the LiteSVM program tests
+# The mock MXE loads it from artifacts/arcium_program.so; *.so files are
+# not committed, so run this once before `cargo test --test program_test`
+
+set -e
+
+# Must match arcium_anchor's ARCIUM_PROG_ID for the arcium-anchor version in Cargo.toml
+ARCIUM_PROGRAM_ID="${ARCIUM_PROGRAM_ID:-BKck65TgoKRokMjQM3datB9oRwJ8rAj2jxPXvHXUvcL6}"
+CLUSTER="${CLUSTER:-devnet}"
+OUTPUT="artifacts/arcium_program.so"
+
+echo "📦 Dumping Arcium program $ARCIUM_PROGRAM_ID from $CLUSTER..."
+mkdir -p artifacts
+solana program dump -u "$CLUSTER" "$ARCIUM_PROGRAM_ID" "$OUTPUT"
+echo "✅ Wrote $OUTPUT"
@@ -52,8 +52,10 @@ export class BuriedTreasureClient {
   private cipher: Cipher;
   private clientKeypair: Keypair;
   private mxePublicKey: Uint8Array;
+  private gameId: anchor.BN;
 
-  constructor(wallet: anchor.Wallet) {
+  constructor(wallet: anchor.Wallet, gameId: anchor.BN = new anchor.BN(0)) {
+    this.gameId = gameId;
     this.connection = new Connection(RPC_URL, "confirmed");
     this.provider = new anchor.AnchorProvider(this.connection, wallet, {
       commitment: "confirmed",
@@ -112,14 +114,12 @@ export class BuriedTreasureClient {
   /**
    * Create a new game and initialize encrypted map via MPC
    */
-  async createGame(seed: number): Promise<string> {
+  async createGame(): Promise<string> {
     const gamePDA = this.getGamePDA();
     const computationOffset = BigInt(Date.now());
 
-    // Encrypt the seed — even the map seed is private
-    const seedBuffer = Buffer.alloc(8);
-    seedBuffer.writeBigUInt64LE(BigInt(seed));
-    const { ciphertext, nonce, clientPublicKey } = this.encryptInput(seedBuffer);
+    // No seed is sent: the MPC cluster draws it, so not even the
+    // authority creating the game can know the map
 
     // Get Arcium PDA addresses
     const compDefIndex = Buffer.from(getCompDefAccOffset("init_map")).readUInt32LE();
@@ -131,9 +131,31 @@ export class BuriedTreasureClient {
     const tx = await this.program.methods
       .createGame(
         new anchor.BN(computationOffset.toString()),
-        Array.from(ciphertext[0]) as any,
-        Array.from(clientPublicKey) as any,
-        nonce
+        this.gameId,
+        new anchor.BN(0), // no deadline
+        new anchor.BN(0), // no turn order
+        {
+          width: 10,
+          height: 10,
+          treasureCount: 15,
+          trapCount: 10,
+          treasureMin: 5,
+          treasureMax: 50,
+          trapMin: 5,
+          trapMax: 30,
+          levels: 1,
+          wallCount: 0,
+          springCount: 0,
+          portalPairs: 0,
+          itemCount: 0,
+        },
+        {
+          entryFeeLamports: new anchor.BN(0), // free to join
+          rakeBps: 0,
+          maxPlayers: 0, // unlimited
+          payoutCurve: { proportional: {} },
+        },
+        { maxActionPoints: 0, slotsPerPoint: new anchor.BN(0) } // no cooldown
       )
       .accountsPartial({
         game: gamePDA,
@@ -234,8 +256,9 @@ export class BuriedTreasureClient {
     const decrypted = this.decryptResult(event.encryptedResult);
 
     return {
-      newX: decrypted.readUInt8(0),
-      newY: decrypted.readUInt8(1),
+      status: actionStatus(decrypted.readUInt8(0)),
+      newX: decrypted.readUInt8(1),
+      newY: decrypted.readUInt8(2),
     };
   }
 
@@ -277,18 +300,28 @@ export class BuriedTreasureClient {
     const event = await resultPromise;
     const decrypted = this.decryptResult(event.encryptedResult);
 
-    const tileType = decrypted.readUInt8(0);
-    const value = decrypted.readUInt16LE(1);
+    const status = actionStatus(decrypted.readUInt8(0));
+    const tileType = tileTypeName(decrypted.readUInt8(1));
+    const value = decrypted.readUInt16LE(2);
+    const health = decrypted.readUInt16LE(4);
+
+    const messages: Record<TileType, string> = {
+      empty: "This tile is empty.",
+      treasure: `You found treasure! +${value} gold`,
+      trap: `You triggered a trap! -${value} health`,
+      staircase: "A staircase leads down.",
+      wall: "A wall blocks the way.",
+      spring: `A healing spring! Health is now ${health}`,
+      portal: `A portal — it leads to tile ${value}`,
+      cache: `An item cache — dig here for a ${ITEM_KINDS[value] ?? "mystery item"}`,
+    };
 
     return {
-      tileType: tileType === 0 ? "empty" : tileType === 1 ? "treasure" : "trap",
+      status,
+      tileType,
       value,
-      message:
-        tileType === 0
-          ? "This tile is empty."
-          : tileType === 1
-          ? `You found treasure! +${value} gold`
-          : `You triggered a trap! -${value} health`,
+      health,
+      message: messages[tileType],
     };
   }
 
@@ -336,8 +369,10 @@ export class BuriedTreasureClient {
     const event = await resultPromise;
     const decrypted = this.decryptResult(event.encryptedResult);
 
+    const status = actionStatus(decrypted.readUInt8(0));
     return {
-      success: decrypted.readUInt8(0) === 1,
+      status,
+      success: status === "ok",
       newGold: decrypted.readUInt16LE(1),
     };
   }
@@ -382,21 +417,79 @@ export class BuriedTreasureClient {
     const event = await resultPromise;
     const decrypted = this.decryptResult(event.encryptedResult);
 
-    const foundType = decrypted.readUInt8(0);
-    const totalValue = decrypted.readUInt16LE(1);
-    const healthLost = decrypted.readUInt16LE(3);
+    const status = actionStatus(decrypted.readUInt8(0));
+    const foundType = tileTypeName(decrypted.readUInt8(1));
+    const totalValue = decrypted.readUInt16LE(2);
+    const healthLost = decrypted.readUInt16LE(4);
+    const healthRestored = decrypted.readUInt16LE(6);
+    const item = ITEM_KINDS[decrypted.readUInt8(8)] ?? null;
+
+    const messages: Record<TileType, string> = {
+      empty: "Nothing found here.",
+      treasure: `Found loot! +${totalValue} gold`,
+      trap: `Trap triggered! -${healthLost} health`,
+      staircase: "A staircase — it can't be dug away.",
+      wall: "Solid wall.",
+      spring: `The spring heals you +${healthRestored} health, then runs dry`,
+      portal: "A portal — it can't be dug away.",
+      cache: `Dug up a ${item}!`,
+    };
 
     return {
-      foundType:
-        foundType === 0 ? "nothing" : foundType === 1 ? "treasure" : "trap",
+      status,
+      foundType,
       totalValue,
       healthLost,
-      message:
-        foundType === 0
-          ? "Nothing found here."
-          : foundType === 1
-          ? `Found loot! +${totalValue} gold`
-          : `Trap triggered! -${healthLost} health`,
+      healthRestored,
+      item,
+      message: messages[foundType],
+    };
+  }
+
+  /**
+   * Use an item from the encrypted inventory
+   * The target only matters for the shovel. The trap detector answers only
+   * whether some adjacent tile is trapped — never which one.
+   */
+  async useItem(item: ItemKind, targetX = 0, targetY = 0): Promise<UseItemResultData> {
+    const computationOffset = BigInt(Date.now());
+
+    const inputBuffer = Buffer.alloc(3);
+    inputBuffer.writeUInt8(ITEM_KINDS.indexOf(item), 0);
+    inputBuffer.writeUInt8(targetX, 1);
+    inputBuffer.writeUInt8(targetY, 2);
+    const { ciphertext, nonce, clientPublicKey } = this.encryptInput(inputBuffer);
+
+    const resultPromise = this.awaitEvent("UseItemResultEvent");
+
+    const tx = await this.program.methods
+      .useItem(
+        new anchor.BN(computationOffset.toString()),
+        Array.from(ciphertext[0]) as any,
+        Array.from(clientPublicKey) as any,
+        nonce
+      )
+      .accountsPartial({
+        playerAccount: this.getPlayerPDA(),
+        game: this.getGamePDA(),
+        player: this.provider.wallet.publicKey,
+      })
+      .rpc();
+
+    await awaitComputationFinalization(
+      this.provider as anchor.AnchorProvider,
+      computationOffset,
+      PROGRAM_ID,
+      "confirmed"
+    );
+
+    const event = await resultPromise;
+    const decrypted = this.decryptResult(event.encryptedResult);
+
+    return {
+      status: actionStatus(decrypted.readUInt8(0)),
+      item,
+      value: decrypted.readUInt16LE(2),
     };
   }
 
@@ -406,7 +499,11 @@ export class BuriedTreasureClient {
 
   private getGamePDA(): PublicKey {
     const [pda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("game"), this.provider.wallet.publicKey.toBuffer()],
+      [
+        Buffer.from("game"),
+        this.provider.wallet.publicKey.toBuffer(),
+        this.gameId.toArrayLike(Buffer, "le", 8),
+      ],
       PROGRAM_ID
     );
     return pda;
@@ -448,26 +545,107 @@ export class BuriedTreasureClient {
 // RESULT TYPES
 // =========================================================
 
+/**
+ * Sealed status code every action result starts with, in circuit order.
+ * A rejected action changes nothing; only this player can see why.
+ */
+export type ActionStatus =
+  | "ok"
+  | "notAdjacent"
+  | "outOfBounds"
+  | "notEnoughGold"
+  | "invalidAmount"
+  | "pileFull"
+  | "dead"
+  | "noStairs"
+  | "blocked"
+  | "noItem"
+  | "noWall";
+
+const ACTION_STATUSES: ActionStatus[] = [
+  "ok",
+  "notAdjacent",
+  "outOfBounds",
+  "notEnoughGold",
+  "invalidAmount",
+  "pileFull",
+  "dead",
+  "noStairs",
+  "blocked",
+  "noItem",
+  "noWall",
+];
+
+function actionStatus(code: number): ActionStatus {
+  return ACTION_STATUSES[code] ?? "ok";
+}
+
+export type TileType =
+  | "empty"
+  | "treasure"
+  | "trap"
+  | "staircase"
+  | "wall"
+  | "spring"
+  | "portal"
+  | "cache";
+
+// Indexed by `MapTile.tile_type`; a dig finding nothing reports 0, "empty"
+const TILE_TYPES: TileType[] = [
+  "empty",
+  "treasure",
+  "trap",
+  "staircase",
+  "wall",
+  "spring",
+  "portal",
+  "cache",
+];
+
+function tileTypeName(code: number): TileType {
+  return TILE_TYPES[code] ?? "empty";
+}
+
+export type ItemKind = "shovel" | "trapDetector" | "mapFragment" | "potion";
+
+// Indexed by item kind, as in `PlayerState.inventory`
+const ITEM_KINDS: ItemKind[] = ["shovel", "trapDetector", "mapFragment", "potion"];
+
 export interface MoveResultData {
+  status: ActionStatus;
   newX: number;
   newY: number;
 }
 
 export interface ExploreResultData {
-  tileType: "empty" | "treasure" | "trap";
-  value: number;
+  status: ActionStatus;
+  tileType: TileType;
+  value: number; // gold, damage, healing, or a portal's exit tile index
+  health: number;
   message: string;
 }
 
+export interface UseItemResultData {
+  status: ActionStatus;
+  item: ItemKind;
+  // trapDetector: 1 if a trap is adjacent; mapFragment: staircase tile index
+  // (144 = none on this level); potion: new health
+  value: number;
+}
+
 export interface BuryResultData {
+  status: ActionStatus;
   success: boolean;
   newGold: number;
 }
 
 export interface DigResultData {
-  foundType: "nothing" | "treasure" | "trap";
+  status: ActionStatus;
+  foundType: TileType;
   totalValue: number;
   healthLost: number;
+  healthRestored: number;
+  item: ItemKind | null; // picked up from a cache
   message: string;
 }
 
@@ -483,7 +661,7 @@ const client = new BuriedTreasureClient(wallet);
 await client.initialize();
 
 // Create game (game authority only)
-await client.createGame(Date.now());
+await client.createGame();
 
 // Register as player
 await client.registerPlayer();
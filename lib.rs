@@ -4,7 +4,10 @@
 // ============================================================
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::get_instruction_relative;
+use anchor_lang::system_program;
 use arcium_anchor::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
 // Computation definition offsets (generated from Arcis function names)
 const COMP_DEF_OFFSET_INIT_MAP: u32 = comp_def_offset("init_map");
@@ -14,6 +17,41 @@ const COMP_DEF_OFFSET_MOVE_PLAYER: u32 = comp_def_offset("move_player");
 const COMP_DEF_OFFSET_EXPLORE: u32 = comp_def_offset("explore");
 const COMP_DEF_OFFSET_BURY: u32 = comp_def_offset("bury");
 const COMP_DEF_OFFSET_DIG: u32 = comp_def_offset("dig");
+const COMP_DEF_OFFSET_DESCEND: u32 = comp_def_offset("descend");
+const COMP_DEF_OFFSET_USE_ITEM: u32 = comp_def_offset("use_item");
+const COMP_DEF_OFFSET_REVEAL_STANDINGS: u32 = comp_def_offset("reveal_standings");
+
+// Tile capacity shared by all map tiers (mirrors `MAX_TILES` in the circuits)
+const MAX_MAP_TILES: usize = 144;
+
+// Supported (width, height) tiers — each must fit in MAX_MAP_TILES
+const MAP_TIERS: [(u8, u8); 3] = [(8, 8), (10, 10), (12, 12)];
+
+// Deepest dungeon a game may have; each level has its own map and buried layer
+const MAX_LEVELS: u8 = 3;
+
+// Portal pairs init_map can link (mirrors `MAX_PORTAL_PAIRS` in the circuits)
+const MAX_PORTAL_PAIRS: u8 = 4;
+
+// Ciphertext counts of the persisted encrypted state (one ciphertext per field)
+const MAP_CIPHERTEXTS: usize = MAX_MAP_TILES * 2; // tile_type + value
+const BURIED_CIPHERTEXTS: usize = MAX_MAP_TILES;
+const PLAYER_STATE_CIPHERTEXTS: usize = 9; // x, y, gold, health, level, 4 item counts
+const VISITED_CIPHERTEXTS: usize = MAX_MAP_TILES;
+
+// Rake is in basis points of each prize pool, capped at 10%
+const BPS_DENOMINATOR: u16 = 10_000;
+const MAX_RAKE_BPS: u16 = 1_000;
+
+// PayoutCurve::TopThree split, in percent
+const TOP_THREE_WEIGHTS: [u64; 3] = [50, 30, 20];
+
+// Seconds before abort_pending may release an action whose callback never came
+const PENDING_TIMEOUT: i64 = 300;
+
+// Upper bounds on game and turn length, in seconds, so deadlines never overflow
+const MAX_GAME_DURATION: i64 = 365 * 24 * 60 * 60;
+const MAX_TURN_DURATION: i64 = 24 * 60 * 60;
 
 declare_id!("EdRkkqe1psz9MJJtSSFcpC99h9VWzT6MvLTGjDG3ppyA");
 
@@ -60,62 +98,276 @@ pub mod buried_treasure {
         Ok(())
     }
 
+    pub fn init_descend_comp_def(ctx: Context<InitDescendCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, COMP_DEF_OFFSET_DESCEND, None, None)?;
+        Ok(())
+    }
+
+    pub fn init_use_item_comp_def(ctx: Context<InitUseItemCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, COMP_DEF_OFFSET_USE_ITEM, None, None)?;
+        Ok(())
+    }
+
+    pub fn init_reveal_standings_comp_def(ctx: Context<InitRevealStandingsCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, COMP_DEF_OFFSET_REVEAL_STANDINGS, None, None)?;
+        Ok(())
+    }
+
     // =========================================================
     // GAME SETUP
     // =========================================================
 
-    /// Create a new game instance and initialize the encrypted map
+    /// Create a new game instance and initialize the surface level's
+    /// encrypted map; init_level sets up any levels below it. The map
+    /// seed is drawn inside MPC, so the authority never knows it.
     pub fn create_game(
         ctx: Context<CreateGame>,
         computation_offset: u64,
-        ciphertext_seed: [u8; 32],
-        pub_key: [u8; 32],
-        nonce: u128,
+        game_id: u64,
+        duration: i64,
+        turn_duration: i64,
+        config: GameConfig,
+        economics: Economics,
+        cooldown: CooldownConfig,
     ) -> Result<()> {
+        config.validate()?;
+        economics.validate()?;
+        cooldown.validate()?;
+        require!(duration <= MAX_GAME_DURATION, GameError::InvalidDuration);
+        require!(turn_duration <= MAX_TURN_DURATION, GameError::InvalidDuration);
+
         // Initialize game account with public metadata
         let game = &mut ctx.accounts.game;
         game.authority = ctx.accounts.authority.key();
+        game.game_id = game_id;
         game.player_count = 0;
         game.created_at = Clock::get()?.unix_timestamp;
+        game.ends_at = if duration > 0 { game.created_at + duration } else { 0 };
+        game.turn_duration = turn_duration.max(0);
+        game.turn_seat = 0;
+        game.turn_started_at = game.created_at;
+        // Counted in as each level's map lands
+        game.treasures_remaining = 0;
+        game.levels_ready = 0;
+        game.config = config;
+        game.economics = economics;
+        game.cooldown = cooldown;
+        game.mint = Pubkey::default();
+        game.entry_fee = 0;
+        game.action_sequence = 0;
         game.is_active = true;
+        game.bump = ctx.bumps.game;
+        let sequence = sequence_computation(game, computation_offset);
 
-        // Queue MPC computation to initialize encrypted map
-        let args = vec![
-            Argument::ArcisPubkey(pub_key),
-            Argument::PlaintextU128(nonce),
-            Argument::Ciphertext(ciphertext_seed.to_vec()),
-        ];
+        // Ciphertexts stay zeroed until init_map_callback writes the map
+        let mut encrypted_map = ctx.accounts.encrypted_map.load_init()?;
+        encrypted_map.game = game.key();
+        encrypted_map.level = 0;
+        encrypted_map.pending_computation = computation_offset.to_le_bytes();
+        encrypted_map.pending_sequence = sequence.to_le_bytes();
+        encrypted_map.bump = ctx.bumps.encrypted_map;
+        drop(encrypted_map);
+
+        // Queue MPC computation to initialize encrypted map — the config is
+        // its only input; the seed comes from the cluster's own randomness
+        let mut args = game.config.as_arguments().to_vec();
+        args.push(Argument::PlaintextU8(0));
 
         queue_computation(
             ctx.accounts,
             COMP_DEF_OFFSET_INIT_MAP,
             computation_offset,
             args,
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_map.key(),
+                    is_writable: true,
+                },
+            ],
         )?;
 
         emit!(GameCreated {
             game: game.key(),
             authority: game.authority,
+            game_id: game.game_id,
             timestamp: game.created_at,
         });
 
         Ok(())
     }
 
-    /// Initialize the buried loot layer (separate MPC call)
+    /// Initialize the encrypted map of one level below the surface, with
+    /// richer treasures and nastier traps the deeper it is
+    pub fn init_level(
+        ctx: Context<InitLevel>,
+        computation_offset: u64,
+        level: u8,
+    ) -> Result<()> {
+        require!(
+            level > 0 && level < ctx.accounts.game.config.levels,
+            GameError::InvalidLevel
+        );
+
+        let sequence = sequence_computation(&mut ctx.accounts.game, computation_offset);
+        let mut encrypted_map = ctx.accounts.encrypted_map.load_init()?;
+        encrypted_map.game = ctx.accounts.game.key();
+        encrypted_map.level = level;
+        encrypted_map.pending_computation = computation_offset.to_le_bytes();
+        encrypted_map.pending_sequence = sequence.to_le_bytes();
+        encrypted_map.bump = ctx.bumps.encrypted_map;
+        drop(encrypted_map);
+
+        let mut args = ctx.accounts.game.config.as_arguments().to_vec();
+        args.push(Argument::PlaintextU8(level));
+
+        queue_computation(
+            ctx.accounts,
+            COMP_DEF_OFFSET_INIT_MAP,
+            computation_offset,
+            args,
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_map.key(),
+                    is_writable: true,
+                },
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Initialize one level's buried loot layer (separate MPC call)
     pub fn init_buried(
         ctx: Context<InitBuried>,
         computation_offset: u64,
+        level: u8,
+    ) -> Result<()> {
+        require!(level < ctx.accounts.game.config.levels, GameError::InvalidLevel);
+
+        let sequence = sequence_computation(&mut ctx.accounts.game, computation_offset);
+        let mut buried_layer = ctx.accounts.encrypted_buried_layer.load_init()?;
+        buried_layer.game = ctx.accounts.game.key();
+        buried_layer.level = level;
+        buried_layer.pending_computation = computation_offset.to_le_bytes();
+        buried_layer.pending_sequence = sequence.to_le_bytes();
+        buried_layer.bump = ctx.bumps.encrypted_buried_layer;
+        drop(buried_layer);
+
+        queue_computation(
+            ctx.accounts,
+            COMP_DEF_OFFSET_INIT_BURIED_LAYER,
+            computation_offset,
+            vec![],
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_buried_layer.key(),
+                    is_writable: true,
+                },
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Queue init_map again for a level whose map never landed, reusing its
+    /// account. Whatever was queued before is superseded: its output, if it
+    /// ever arrives, is rejected.
+    pub fn retry_level_init(
+        ctx: Context<RetryLevelInit>,
+        computation_offset: u64,
+        level: u8,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.game.is_running(now), GameError::GameNotActive);
+        require!(level < ctx.accounts.game.config.levels, GameError::InvalidLevel);
+
+        let sequence = sequence_computation(&mut ctx.accounts.game, computation_offset);
+        let mut encrypted_map = ctx.accounts.encrypted_map.load_mut()?;
+        encrypted_map.pending_computation = computation_offset.to_le_bytes();
+        encrypted_map.pending_sequence = sequence.to_le_bytes();
+        drop(encrypted_map);
+
+        let mut args = ctx.accounts.game.config.as_arguments().to_vec();
+        args.push(Argument::PlaintextU8(level));
+
+        queue_computation(
+            ctx.accounts,
+            COMP_DEF_OFFSET_INIT_MAP,
+            computation_offset,
+            args,
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_map.key(),
+                    is_writable: true,
+                },
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Queue init_buried_layer again for a layer that never landed, as
+    /// retry_level_init does for the map
+    pub fn retry_buried_init(
+        ctx: Context<RetryBuriedInit>,
+        computation_offset: u64,
+        level: u8,
     ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.game.is_running(now), GameError::GameNotActive);
+        require!(level < ctx.accounts.game.config.levels, GameError::InvalidLevel);
+
+        let sequence = sequence_computation(&mut ctx.accounts.game, computation_offset);
+        let mut buried_layer = ctx.accounts.encrypted_buried_layer.load_mut()?;
+        buried_layer.pending_computation = computation_offset.to_le_bytes();
+        buried_layer.pending_sequence = sequence.to_le_bytes();
+        drop(buried_layer);
+
         queue_computation(
             ctx.accounts,
             COMP_DEF_OFFSET_INIT_BURIED_LAYER,
             computation_offset,
             vec![],
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_buried_layer.key(),
+                    is_writable: true,
+                },
+            ],
         )?;
         Ok(())
     }
 
+    /// Put real stakes on the game: every player deposits `entry_fee` of
+    /// `mint` into the game vault when registering. Gold stays in-game
+    /// (bury only moves encrypted gold); the vault is paid out under the
+    /// game's payout curve through claim_winnings. Only before anyone has joined.
+    pub fn init_vault(ctx: Context<InitVault>, entry_fee: u64) -> Result<()> {
+        let game = &mut ctx.accounts.game;
+        require!(game.player_count == 0 && entry_fee > 0, GameError::InvalidStake);
+
+        game.mint = ctx.accounts.mint.key();
+        game.entry_fee = entry_fee;
+        Ok(())
+    }
+
     // =========================================================
     // PLAYER ACTIONS
     // =========================================================
@@ -125,18 +377,100 @@ pub mod buried_treasure {
         ctx: Context<RegisterPlayer>,
         computation_offset: u64,
     ) -> Result<()> {
+        // Only a fresh player account has no wallet yet
+        require!(
+            ctx.accounts.player_account.wallet == Pubkey::default(),
+            GameError::PlayerAlreadyRegistered
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let economics = ctx.accounts.game.economics;
+        require!(ctx.accounts.game.is_running(now), GameError::GameNotActive);
+        require!(
+            economics.max_players == 0 || ctx.accounts.game.player_count < economics.max_players,
+            GameError::GameFull
+        );
+
+        // Lamport entry fee goes into the game account itself. The wallet
+        // must stay rent-exempt once it has paid, or the runtime would
+        // reject the transaction with a less helpful error.
+        if economics.entry_fee_lamports > 0 {
+            let rent_exempt = Rent::get()?.minimum_balance(0);
+            require!(
+                ctx.accounts.player.lamports()
+                    >= economics.entry_fee_lamports.saturating_add(rent_exempt),
+                GameError::Underpayment
+            );
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.player.to_account_info(),
+                        to: ctx.accounts.game.to_account_info(),
+                    },
+                ),
+                economics.entry_fee_lamports,
+            )?;
+        }
+
         let player_account = &mut ctx.accounts.player_account;
         player_account.wallet = ctx.accounts.player.key();
         player_account.game = ctx.accounts.game.key();
-        player_account.joined_at = Clock::get()?.unix_timestamp;
+        player_account.joined_at = now;
         // Public stats (non-sensitive, for leaderboard)
         player_account.tiles_explored = 0;
         player_account.treasures_found = 0;
         player_account.traps_triggered = 0;
+        player_account.eliminated = false;
+        player_account.standing_revealed = false;
+        player_account.winnings_claimed = false;
+        player_account.seat = ctx.accounts.game.player_count;
+        // Join with a full bucket of action points
+        player_account.last_action_slot = Clock::get()?.slot;
+        player_account.action_points = ctx.accounts.game.cooldown.max_action_points;
+        player_account.level = 0;
+        player_account.bump = ctx.bumps.player_account;
 
         // Increment game player count
         let game = &mut ctx.accounts.game;
         game.player_count += 1;
+        // The first player's turn starts when they join
+        if game.player_count == 1 {
+            game.turn_started_at = now;
+        }
+
+        // Staked games take the entry fee up front
+        if game.is_staked() {
+            let (Some(player_token_account), Some(vault), Some(token_program)) = (
+                &ctx.accounts.player_token_account,
+                &ctx.accounts.vault,
+                &ctx.accounts.token_program,
+            ) else {
+                return err!(GameError::StakeAccountsMissing);
+            };
+            token::transfer(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: player_token_account.to_account_info(),
+                        to: vault.to_account_info(),
+                        authority: ctx.accounts.player.to_account_info(),
+                    },
+                ),
+                game.entry_fee,
+            )?;
+        }
+
+        let mut player_state = ctx.accounts.encrypted_player_state.load_init()?;
+        player_state.game = game.key();
+        player_state.wallet = player_account.wallet;
+        player_state.bump = ctx.bumps.encrypted_player_state;
+        drop(player_state);
+
+        let mut visited_tiles = ctx.accounts.encrypted_visited_tiles.load_init()?;
+        visited_tiles.game = game.key();
+        visited_tiles.wallet = player_account.wallet;
+        visited_tiles.bump = ctx.bumps.encrypted_visited_tiles;
+        drop(visited_tiles);
 
         // Queue MPC to create encrypted player state
         queue_computation(
@@ -144,6 +478,24 @@ pub mod buried_treasure {
             COMP_DEF_OFFSET_REGISTER_PLAYER,
             computation_offset,
             vec![],
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_account.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_player_state.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_visited_tiles.key(),
+                    is_writable: true,
+                },
+            ],
         )?;
 
         emit!(PlayerRegistered {
@@ -151,34 +503,135 @@ pub mod buried_treasure {
             player: player_account.wallet,
             timestamp: player_account.joined_at,
         });
+        record_action(
+            &mut ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            ActionType::Register,
+            computation_offset,
+            now,
+        );
+
+        Ok(())
+    }
+
+    /// Queue register_player again after the cluster reported it failed.
+    /// The seat and entry fee from the first attempt still stand; until a
+    /// registration lands, every action fails with PlayerNotReady.
+    pub fn retry_registration(
+        ctx: Context<RetryRegistration>,
+        computation_offset: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.game.is_running(now), GameError::GameNotActive);
+        require!(
+            ctx.accounts.encrypted_player_state.load()?.initialized == 0,
+            GameError::PlayerAlreadyRegistered
+        );
+        require!(
+            ctx.accounts.player_account.pending_computation.is_none(),
+            GameError::ComputationPending
+        );
+
+        queue_computation(
+            ctx.accounts,
+            COMP_DEF_OFFSET_REGISTER_PLAYER,
+            computation_offset,
+            vec![],
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_account.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_player_state.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_visited_tiles.key(),
+                    is_writable: true,
+                },
+            ],
+        )?;
 
+        record_action(
+            &mut ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            ActionType::Register,
+            computation_offset,
+            now,
+        );
         Ok(())
     }
 
-    /// Move to an adjacent tile
+    /// Move to an adjacent tile — walls block, portals teleport
     /// Wallet signature required — identity verification
     pub fn move_player(
         ctx: Context<MovePlayer>,
         computation_offset: u64,
-        ciphertext_input: [u8; 32],
+        ciphertext_input: [[u8; 32]; 2],
         pub_key: [u8; 32],
         nonce: u128,
     ) -> Result<()> {
-        require!(ctx.accounts.game.is_active, GameError::GameNotActive);
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        require!(ctx.accounts.game.is_running(now), GameError::GameNotActive);
+        require!(!ctx.accounts.player_account.eliminated, GameError::PlayerEliminated);
+        require!(
+            ctx.accounts.player_account.pending_computation.is_none(),
+            GameError::ComputationPending
+        );
+        let cooldown = ctx.accounts.game.cooldown;
+        ctx.accounts.player_account.spend_action_point(&cooldown, clock.slot)?;
+        take_turn(&mut ctx.accounts.game, ctx.accounts.player_account.seat, now)?;
 
-        let args = vec![
+        let player_state = ctx.accounts.encrypted_player_state.load()?;
+        let map = ctx.accounts.encrypted_map.load()?;
+        let mut args = vec![
             Argument::ArcisPubkey(pub_key),
             Argument::PlaintextU128(nonce),
-            Argument::Ciphertext(ciphertext_input.to_vec()),
+            Argument::EncryptedU8(ciphertext_input[0]),
+            Argument::EncryptedU8(ciphertext_input[1]),
         ];
+        args.extend(player_state.as_arguments(ctx.accounts.encrypted_player_state.key()));
+        args.extend(map.as_arguments(ctx.accounts.encrypted_map.key()));
+        args.extend(ctx.accounts.game.config.as_arguments());
+        args.extend(requester_arguments(pub_key, nonce));
+        drop(player_state);
+        drop(map);
 
         queue_computation(
             ctx.accounts,
             COMP_DEF_OFFSET_MOVE_PLAYER,
             computation_offset,
             args,
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_account.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_player_state.key(),
+                    is_writable: true,
+                },
+            ],
         )?;
 
+        record_action(
+            &mut ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            ActionType::Move,
+            computation_offset,
+            now,
+        );
+
         Ok(())
     }
 
@@ -186,25 +639,77 @@ pub mod buried_treasure {
     pub fn explore(
         ctx: Context<Explore>,
         computation_offset: u64,
-        ciphertext_input: [u8; 32],
+        ciphertext_input: [[u8; 32]; 2],
         pub_key: [u8; 32],
         nonce: u128,
     ) -> Result<()> {
-        require!(ctx.accounts.game.is_active, GameError::GameNotActive);
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        require!(ctx.accounts.game.is_running(now), GameError::GameNotActive);
+        require!(!ctx.accounts.player_account.eliminated, GameError::PlayerEliminated);
+        require!(
+            ctx.accounts.player_account.pending_computation.is_none(),
+            GameError::ComputationPending
+        );
+        let cooldown = ctx.accounts.game.cooldown;
+        ctx.accounts.player_account.spend_action_point(&cooldown, clock.slot)?;
+        take_turn(&mut ctx.accounts.game, ctx.accounts.player_account.seat, now)?;
 
-        let args = vec![
+        let player_state = ctx.accounts.encrypted_player_state.load()?;
+        let map = ctx.accounts.encrypted_map.load()?;
+        let visited_tiles = ctx.accounts.encrypted_visited_tiles.load()?;
+        let mut args = vec![
             Argument::ArcisPubkey(pub_key),
             Argument::PlaintextU128(nonce),
-            Argument::Ciphertext(ciphertext_input.to_vec()),
+            Argument::EncryptedU8(ciphertext_input[0]),
+            Argument::EncryptedU8(ciphertext_input[1]),
         ];
+        args.extend(player_state.as_arguments(ctx.accounts.encrypted_player_state.key()));
+        args.extend(map.as_arguments(ctx.accounts.encrypted_map.key()));
+        args.extend(visited_tiles.as_arguments(ctx.accounts.encrypted_visited_tiles.key()));
+        args.extend(ctx.accounts.game.config.as_arguments());
+        args.extend(requester_arguments(pub_key, nonce));
+        drop(player_state);
+        drop(map);
+        drop(visited_tiles);
 
         queue_computation(
             ctx.accounts,
             COMP_DEF_OFFSET_EXPLORE,
             computation_offset,
             args,
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_account.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_player_state.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_map.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_visited_tiles.key(),
+                    is_writable: true,
+                },
+            ],
         )?;
 
+        record_action(
+            &mut ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            ActionType::Explore,
+            computation_offset,
+            now,
+        );
+
         Ok(())
     }
 
@@ -212,33 +717,72 @@ pub mod buried_treasure {
     pub fn bury(
         ctx: Context<Bury>,
         computation_offset: u64,
-        ciphertext_input: [u8; 32],
+        ciphertext_input: [[u8; 32]; 3],
         pub_key: [u8; 32],
         nonce: u128,
     ) -> Result<()> {
-        require!(ctx.accounts.game.is_active, GameError::GameNotActive);
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        require!(ctx.accounts.game.is_running(now), GameError::GameNotActive);
+        require!(!ctx.accounts.player_account.eliminated, GameError::PlayerEliminated);
+        require!(
+            ctx.accounts.player_account.pending_computation.is_none(),
+            GameError::ComputationPending
+        );
+        let cooldown = ctx.accounts.game.cooldown;
+        ctx.accounts.player_account.spend_action_point(&cooldown, clock.slot)?;
+        take_turn(&mut ctx.accounts.game, ctx.accounts.player_account.seat, now)?;
 
-        let args = vec![
+        let player_state = ctx.accounts.encrypted_player_state.load()?;
+        let buried_layer = ctx.accounts.encrypted_buried_layer.load()?;
+        let mut args = vec![
             Argument::ArcisPubkey(pub_key),
             Argument::PlaintextU128(nonce),
-            Argument::Ciphertext(ciphertext_input.to_vec()),
+            Argument::EncryptedU8(ciphertext_input[0]),
+            Argument::EncryptedU8(ciphertext_input[1]),
+            Argument::EncryptedU16(ciphertext_input[2]),
         ];
+        args.extend(player_state.as_arguments(ctx.accounts.encrypted_player_state.key()));
+        args.extend(buried_layer.as_arguments(ctx.accounts.encrypted_buried_layer.key()));
+        args.extend(ctx.accounts.game.config.as_arguments());
+        args.extend(requester_arguments(pub_key, nonce));
+        drop(player_state);
+        drop(buried_layer);
 
         queue_computation(
             ctx.accounts,
             COMP_DEF_OFFSET_BURY,
             computation_offset,
             args,
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_account.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_player_state.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_buried_layer.key(),
+                    is_writable: true,
+                },
+            ],
         )?;
 
-        // NOTE: No tile coordinates or amounts in the event!
+        // NOTE: No tile coordinates or amounts in the events!
         // Only that a bury action occurred
-        emit!(ActionPerformed {
-            game: ctx.accounts.game.key(),
-            player: ctx.accounts.player.key(),
-            action_type: ActionType::Bury,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+        record_action(
+            &mut ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            ActionType::Bury,
+            computation_offset,
+            now,
+        );
 
         Ok(())
     }
@@ -247,91 +791,1275 @@ pub mod buried_treasure {
     pub fn dig(
         ctx: Context<Dig>,
         computation_offset: u64,
-        ciphertext_input: [u8; 32],
+        ciphertext_input: [[u8; 32]; 2],
         pub_key: [u8; 32],
         nonce: u128,
     ) -> Result<()> {
-        require!(ctx.accounts.game.is_active, GameError::GameNotActive);
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        require!(ctx.accounts.game.is_running(now), GameError::GameNotActive);
+        require!(!ctx.accounts.player_account.eliminated, GameError::PlayerEliminated);
+        require!(
+            ctx.accounts.player_account.pending_computation.is_none(),
+            GameError::ComputationPending
+        );
+        let cooldown = ctx.accounts.game.cooldown;
+        ctx.accounts.player_account.spend_action_point(&cooldown, clock.slot)?;
+        take_turn(&mut ctx.accounts.game, ctx.accounts.player_account.seat, now)?;
 
-        let args = vec![
+        let player_state = ctx.accounts.encrypted_player_state.load()?;
+        let map = ctx.accounts.encrypted_map.load()?;
+        let buried_layer = ctx.accounts.encrypted_buried_layer.load()?;
+        let mut args = vec![
             Argument::ArcisPubkey(pub_key),
             Argument::PlaintextU128(nonce),
-            Argument::Ciphertext(ciphertext_input.to_vec()),
+            Argument::EncryptedU8(ciphertext_input[0]),
+            Argument::EncryptedU8(ciphertext_input[1]),
         ];
+        args.extend(player_state.as_arguments(ctx.accounts.encrypted_player_state.key()));
+        args.extend(map.as_arguments(ctx.accounts.encrypted_map.key()));
+        args.extend(buried_layer.as_arguments(ctx.accounts.encrypted_buried_layer.key()));
+        args.extend(ctx.accounts.game.config.as_arguments());
+        args.extend(requester_arguments(pub_key, nonce));
+        drop(player_state);
+        drop(map);
+        drop(buried_layer);
 
         queue_computation(
             ctx.accounts,
             COMP_DEF_OFFSET_DIG,
             computation_offset,
             args,
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_account.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_player_state.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_map.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_buried_layer.key(),
+                    is_writable: true,
+                },
+            ],
+        )?;
+
+        record_action(
+            &mut ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            ActionType::Dig,
+            computation_offset,
+            now,
+        );
+
+        Ok(())
+    }
+
+    /// Take the staircase the player stands on down to the next level.
+    /// The MPC checks the staircase; the program only refuses players
+    /// already on the deepest level.
+    pub fn descend(
+        ctx: Context<Descend>,
+        computation_offset: u64,
+        pub_key: [u8; 32],
+        nonce: u128,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        require!(ctx.accounts.game.is_running(now), GameError::GameNotActive);
+        require!(!ctx.accounts.player_account.eliminated, GameError::PlayerEliminated);
+        require!(
+            ctx.accounts.player_account.pending_computation.is_none(),
+            GameError::ComputationPending
+        );
+        require!(
+            ctx.accounts.player_account.level + 1 < ctx.accounts.game.config.levels,
+            GameError::NoLevelBelow
+        );
+        // Both the map and the buried layer below must have landed; a level
+        // whose init failed can never be reached
+        let (Some(next_map), Some(next_buried_layer)) =
+            (&ctx.accounts.next_map, &ctx.accounts.next_buried_layer)
+        else {
+            return err!(GameError::LevelNotReady);
+        };
+        require!(
+            next_map.load()?.initialized == 1 && next_buried_layer.load()?.initialized == 1,
+            GameError::LevelNotReady
+        );
+        let cooldown = ctx.accounts.game.cooldown;
+        ctx.accounts.player_account.spend_action_point(&cooldown, clock.slot)?;
+        take_turn(&mut ctx.accounts.game, ctx.accounts.player_account.seat, now)?;
+
+        let player_state = ctx.accounts.encrypted_player_state.load()?;
+        let map = ctx.accounts.encrypted_map.load()?;
+        let visited_tiles = ctx.accounts.encrypted_visited_tiles.load()?;
+        let mut args = Vec::new();
+        args.extend(player_state.as_arguments(ctx.accounts.encrypted_player_state.key()));
+        args.extend(map.as_arguments(ctx.accounts.encrypted_map.key()));
+        args.extend(visited_tiles.as_arguments(ctx.accounts.encrypted_visited_tiles.key()));
+        args.extend(ctx.accounts.game.config.as_arguments());
+        args.extend(requester_arguments(pub_key, nonce));
+        drop(player_state);
+        drop(map);
+        drop(visited_tiles);
+
+        queue_computation(
+            ctx.accounts,
+            COMP_DEF_OFFSET_DESCEND,
+            computation_offset,
+            args,
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_account.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_player_state.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_visited_tiles.key(),
+                    is_writable: true,
+                },
+            ],
+        )?;
+
+        record_action(
+            &mut ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            ActionType::Descend,
+            computation_offset,
+            now,
+        );
+
+        Ok(())
+    }
+
+    /// Use an item from the encrypted inventory. Which item, its target
+    /// and what it did are sealed; the public log shows only `UseItem`.
+    pub fn use_item(
+        ctx: Context<UseItem>,
+        computation_offset: u64,
+        ciphertext_input: [[u8; 32]; 3],
+        pub_key: [u8; 32],
+        nonce: u128,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        require!(ctx.accounts.game.is_running(now), GameError::GameNotActive);
+        require!(!ctx.accounts.player_account.eliminated, GameError::PlayerEliminated);
+        require!(
+            ctx.accounts.player_account.pending_computation.is_none(),
+            GameError::ComputationPending
+        );
+        let cooldown = ctx.accounts.game.cooldown;
+        ctx.accounts.player_account.spend_action_point(&cooldown, clock.slot)?;
+        take_turn(&mut ctx.accounts.game, ctx.accounts.player_account.seat, now)?;
+
+        let player_state = ctx.accounts.encrypted_player_state.load()?;
+        let map = ctx.accounts.encrypted_map.load()?;
+        let mut args = vec![
+            Argument::ArcisPubkey(pub_key),
+            Argument::PlaintextU128(nonce),
+            Argument::EncryptedU8(ciphertext_input[0]),
+            Argument::EncryptedU8(ciphertext_input[1]),
+            Argument::EncryptedU8(ciphertext_input[2]),
+        ];
+        args.extend(player_state.as_arguments(ctx.accounts.encrypted_player_state.key()));
+        args.extend(map.as_arguments(ctx.accounts.encrypted_map.key()));
+        args.extend(ctx.accounts.game.config.as_arguments());
+        args.extend(requester_arguments(pub_key, nonce));
+        drop(player_state);
+        drop(map);
+
+        queue_computation(
+            ctx.accounts,
+            COMP_DEF_OFFSET_USE_ITEM,
+            computation_offset,
+            args,
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_account.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_player_state.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.encrypted_map.key(),
+                    is_writable: true,
+                },
+            ],
+        )?;
+
+        record_action(
+            &mut ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            ActionType::UseItem,
+            computation_offset,
+            now,
+        );
+
+        Ok(())
+    }
+
+    /// Turn mode crank: pass the turn on from a player who let it time out
+    /// or who has been eliminated. Anyone may call it.
+    pub fn skip_turn(ctx: Context<SkipTurn>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let game = &mut ctx.accounts.game;
+        require!(game.is_running(now), GameError::GameNotActive);
+        require!(game.turn_duration > 0, GameError::TurnModeOff);
+        require!(
+            now >= game.turn_started_at + game.turn_duration || ctx.accounts.current_player.eliminated,
+            GameError::TurnNotExpired
+        );
+
+        advance_turn(game, now, true);
+        Ok(())
+    }
+
+    /// Release an action or standing reveal whose callback never arrived,
+    /// so it can be queued again. Its late callback, if any, is then rejected.
+    /// Permissionless once the timeout has passed, like skip_turn, so a
+    /// stuck reveal never depends on its player coming back.
+    pub fn abort_pending(ctx: Context<AbortPending>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let player_account = &mut ctx.accounts.player_account;
+        let Some(computation_offset) = player_account.pending_computation else {
+            return err!(GameError::NoPendingComputation);
+        };
+        require!(
+            now >= player_account.pending_since + PENDING_TIMEOUT,
+            GameError::PendingNotExpired
+        );
+        player_account.pending_computation = None;
+
+        emit!(ComputationFailed {
+            game: ctx.accounts.game.key(),
+            computation_offset,
+            sequence: player_account.pending_sequence,
+            reason: FailureReason::TimedOut,
+        });
+        Ok(())
+    }
+
+    // =========================================================
+    // GAME END
+    // =========================================================
+
+    /// Close the game to further actions. The authority may end it at any
+    /// time; anyone may end it once the deadline has passed or every
+    /// treasure has been claimed.
+    pub fn end_game(ctx: Context<EndGame>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let game = &mut ctx.accounts.game;
+        require!(game.is_active, GameError::GameNotActive);
+
+        // Before the surface map lands there is nothing to claim yet
+        let reason = if game.levels_ready > 0 && game.treasures_remaining == 0 {
+            EndReason::AllTreasuresClaimed
+        } else if game.ends_at != 0 && now >= game.ends_at {
+            EndReason::DeadlineReached
+        } else if ctx.accounts.caller.key() == game.authority {
+            EndReason::Authority
+        } else {
+            return err!(GameError::GameStillRunning);
+        };
+        game.is_active = false;
+
+        let result = &mut ctx.accounts.game_result;
+        result.game = game.key();
+        result.winner = Pubkey::default();
+        result.winning_gold = 0;
+        result.revealed_count = 0;
+        result.claimed_count = 0;
+        result.standings = Vec::with_capacity(game.player_count as usize);
+        let players = game.player_count as u64;
+        let token_total = game.entry_fee.checked_mul(players).ok_or(GameError::Overflow)?;
+        let lamport_total = game
+            .economics
+            .entry_fee_lamports
+            .checked_mul(players)
+            .ok_or(GameError::Overflow)?;
+        result.token_pool = Pool::new(token_total, game.economics.rake_bps);
+        result.lamport_pool = Pool::new(lamport_total, game.economics.rake_bps);
+        result.rake_withdrawn = false;
+        result.bump = ctx.bumps.game_result;
+
+        emit!(GameEnded {
+            game: game.key(),
+            reason,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Reveal one player's final gold into the GameResult.
+    /// Permissionless crank — call once per registered player after end_game,
+    /// and again if the reveal failed or was released by abort_pending.
+    /// Players whose registration never landed go through reveal_unregistered.
+    pub fn reveal_standing(ctx: Context<RevealStanding>, computation_offset: u64) -> Result<()> {
+        require!(!ctx.accounts.game.is_active, GameError::GameStillRunning);
+
+        let player_account = &mut ctx.accounts.player_account;
+        require!(!player_account.standing_revealed, GameError::StandingAlreadyRevealed);
+        require!(
+            player_account.pending_computation.is_none(),
+            GameError::ComputationPending
+        );
+        // Held like an action's, so only this computation's output is
+        // accepted
+        player_account.pending_computation = Some(computation_offset);
+        player_account.pending_sequence =
+            sequence_computation(&mut ctx.accounts.game, computation_offset);
+        player_account.pending_since = Clock::get()?.unix_timestamp;
+
+        let player_state = ctx.accounts.encrypted_player_state.load()?;
+        let args = player_state
+            .as_arguments(ctx.accounts.encrypted_player_state.key())
+            .to_vec();
+        drop(player_state);
+
+        queue_computation(
+            ctx.accounts,
+            COMP_DEF_OFFSET_REVEAL_STANDINGS,
+            computation_offset,
+            args,
+            vec![
+                CallbackAccount {
+                    pubkey: ctx.accounts.player_account.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.game.key(),
+                    is_writable: false,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.game_result.key(),
+                    is_writable: true,
+                },
+            ],
         )?;
 
         Ok(())
     }
 
+    /// Record a zero standing for a player whose registration never landed,
+    /// so the reveal quorum can still close. Permissionless, after end_game.
+    pub fn reveal_unregistered(ctx: Context<RevealUnregistered>) -> Result<()> {
+        require!(!ctx.accounts.game.is_active, GameError::GameStillRunning);
+
+        let player_account = &mut ctx.accounts.player_account;
+        require!(!player_account.standing_revealed, GameError::StandingAlreadyRevealed);
+        require!(
+            player_account.pending_computation.is_none(),
+            GameError::ComputationPending
+        );
+        player_account.standing_revealed = true;
+
+        record_standing(&ctx.accounts.game, &mut ctx.accounts.game_result, player_account.wallet, 0)
+    }
+
+    /// Pay out a player's share of the prize pools (lamport entry fees and,
+    /// for staked games, the token vault) under the game's payout curve.
+    /// Opens once every standing is revealed. Shares round down; the last
+    /// player to claim also collects what the rounding left over.
+    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
+        let result = &mut ctx.accounts.game_result;
+        require!(
+            result.revealed_count == ctx.accounts.game.player_count,
+            GameError::StandingsPending
+        );
+
+        let player_account = &mut ctx.accounts.player_account;
+        require!(!player_account.winnings_claimed, GameError::WinningsAlreadyClaimed);
+        player_account.winnings_claimed = true;
+
+        let (weight, total_weight) =
+            result.payout_weight(ctx.accounts.game.economics.payout_curve, &player_account.wallet)?;
+
+        // The last claim also sweeps the rounding dust earlier shares left
+        result.claimed_count += 1;
+        let last = result.claimed_count == ctx.accounts.game.player_count;
+        let rake_withdrawn = result.rake_withdrawn;
+
+        let lamports = result.lamport_pool.payout(weight, total_weight, last, rake_withdrawn);
+        result.lamport_pool.paid_out += lamports;
+        if lamports > 0 {
+            ctx.accounts.game.sub_lamports(lamports)?;
+            ctx.accounts.player.add_lamports(lamports)?;
+        }
+
+        let game = &ctx.accounts.game;
+        let mut tokens = 0;
+        if game.is_staked() {
+            let (Some(player_token_account), Some(vault), Some(token_program)) = (
+                &ctx.accounts.player_token_account,
+                &ctx.accounts.vault,
+                &ctx.accounts.token_program,
+            ) else {
+                return err!(GameError::StakeAccountsMissing);
+            };
+
+            // The vault must still hold every deposit not yet paid out
+            require!(vault.amount >= result.token_pool.outstanding(), GameError::VaultMismatch);
+
+            tokens = result.token_pool.payout(weight, total_weight, last, rake_withdrawn);
+            result.token_pool.paid_out += tokens;
+            pay_from_vault(game, vault, player_token_account, token_program, tokens)?;
+        }
+
+        emit!(WinningsClaimed {
+            game: game.key(),
+            player: player_account.wallet,
+            lamports,
+            tokens,
+        });
+
+        Ok(())
+    }
+
+    /// Collect the rake from both pools. Authority only, once the game has ended.
+    pub fn withdraw_rake(ctx: Context<WithdrawRake>) -> Result<()> {
+        let result = &mut ctx.accounts.game_result;
+        require!(!result.rake_withdrawn, GameError::RakeAlreadyWithdrawn);
+        result.rake_withdrawn = true;
+
+        let lamports = result.lamport_pool.rake;
+        result.lamport_pool.paid_out += lamports;
+        if lamports > 0 {
+            ctx.accounts.game.sub_lamports(lamports)?;
+            ctx.accounts.authority.add_lamports(lamports)?;
+        }
+
+        let game = &ctx.accounts.game;
+        let tokens = result.token_pool.rake;
+        if game.is_staked() && tokens > 0 {
+            let (Some(authority_token_account), Some(vault), Some(token_program)) = (
+                &ctx.accounts.authority_token_account,
+                &ctx.accounts.vault,
+                &ctx.accounts.token_program,
+            ) else {
+                return err!(GameError::StakeAccountsMissing);
+            };
+            result.token_pool.paid_out += tokens;
+            pay_from_vault(game, vault, authority_token_account, token_program, tokens)?;
+        }
+
+        emit!(RakeWithdrawn {
+            game: game.key(),
+            lamports,
+            tokens,
+        });
+
+        Ok(())
+    }
+
     // =========================================================
     // MPC CALLBACKS — Called by Arcium after computation completes
     // =========================================================
 
-    pub fn explore_callback(ctx: Context<ExploreCallback>, result: Vec<u8>) -> Result<()> {
+    // Every callback verifies the cluster signature over its output before
+    // touching state, so only a finalized computation can write ciphertexts
+    // or bump public stats. Each then consumes the computation it was
+    // queued for, so a signed output cannot be replayed later.
+
+    #[arcium_callback(encrypted_ix = "init_map")]
+    pub fn init_map_callback(
+        ctx: Context<InitMapCallback>,
+        output: SignedComputationOutputs<InitMapOutput>,
+    ) -> Result<()> {
+        let output = verify_action_output(
+            output,
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+            &ctx.accounts.instructions_sysvar,
+        )?;
+
+        let mut encrypted_map = ctx.accounts.encrypted_map.load_mut()?;
+        let computation_offset = encrypted_map.pending_init(&ctx.accounts.computation_account)?;
+        let sequence = u64::from_le_bytes(encrypted_map.pending_sequence);
+        let InitMapOutput { field_0: map } = match output {
+            Ok(output) => output,
+            // The level stays uninitialized until retry_level_init lands it
+            Err(reason) => {
+                return finish_init(&ctx.accounts.game, computation_offset, sequence, Some(reason))
+            }
+        };
+        encrypted_map.initialized = 1;
+        encrypted_map.nonce = map.nonce.to_le_bytes();
+        encrypted_map.ciphertexts = map.ciphertexts;
+
+        // Only treasures on a playable level count towards the end
+        let game = &mut ctx.accounts.game;
+        game.treasures_remaining += game.config.treasure_count as u32;
+        game.levels_ready += 1;
+        finish_init(game, computation_offset, sequence, None)
+    }
+
+    #[arcium_callback(encrypted_ix = "init_buried_layer")]
+    pub fn init_buried_layer_callback(
+        ctx: Context<InitBuriedLayerCallback>,
+        output: SignedComputationOutputs<InitBuriedLayerOutput>,
+    ) -> Result<()> {
+        let output = verify_action_output(
+            output,
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+            &ctx.accounts.instructions_sysvar,
+        )?;
+
+        let mut buried_layer = ctx.accounts.encrypted_buried_layer.load_mut()?;
+        let computation_offset = buried_layer.pending_init(&ctx.accounts.computation_account)?;
+        let sequence = u64::from_le_bytes(buried_layer.pending_sequence);
+        let InitBuriedLayerOutput { field_0: layer } = match output {
+            Ok(output) => output,
+            Err(reason) => {
+                return finish_init(&ctx.accounts.game, computation_offset, sequence, Some(reason))
+            }
+        };
+        buried_layer.initialized = 1;
+        buried_layer.nonce = layer.nonce.to_le_bytes();
+        buried_layer.ciphertexts = layer.ciphertexts;
+        finish_init(&ctx.accounts.game, computation_offset, sequence, None)
+    }
+
+    #[arcium_callback(encrypted_ix = "register_player")]
+    pub fn register_player_callback(
+        ctx: Context<RegisterPlayerCallback>,
+        output: SignedComputationOutputs<RegisterPlayerOutput>,
+    ) -> Result<()> {
+        let Some(RegisterPlayerOutput {
+            field_0: RegisterPlayerOutputStruct0 {
+                field_0: state,
+                field_1: visited,
+            },
+        }) = player_output(
+            output,
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+            &ctx.accounts.instructions_sysvar,
+        )?
+        else {
+            return Ok(());
+        };
+
+        let mut player_state = ctx.accounts.encrypted_player_state.load_mut()?;
+        player_state.nonce = state.nonce.to_le_bytes();
+        player_state.ciphertexts = state.ciphertexts;
+        player_state.initialized = 1;
+        let mut visited_tiles = ctx.accounts.encrypted_visited_tiles.load_mut()?;
+        visited_tiles.nonce = visited.nonce.to_le_bytes();
+        visited_tiles.ciphertexts = visited.ciphertexts;
+
+        finish_action(
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.computation_account,
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "explore")]
+    pub fn explore_callback(
+        ctx: Context<ExploreCallback>,
+        output: SignedComputationOutputs<ExploreOutput>,
+    ) -> Result<()> {
+        let Some(ExploreOutput {
+            field_0: ExploreOutputStruct0 {
+                field_0: result,
+                field_1: state,
+                field_2: map,
+                field_3: visited,
+                field_4: treasure_claimed,
+                field_5: trap_triggered,
+                field_6: eliminated,
+            },
+        }) = player_output(
+            output,
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+            &ctx.accounts.instructions_sysvar,
+        )?
+        else {
+            return Ok(());
+        };
+
+        // Persist updated player state, map and visited tiles
+        let mut player_state = ctx.accounts.encrypted_player_state.load_mut()?;
+        player_state.nonce = state.nonce.to_le_bytes();
+        player_state.ciphertexts = state.ciphertexts;
+        let mut encrypted_map = ctx.accounts.encrypted_map.load_mut()?;
+        encrypted_map.nonce = map.nonce.to_le_bytes();
+        encrypted_map.ciphertexts = map.ciphertexts;
+        let mut visited_tiles = ctx.accounts.encrypted_visited_tiles.load_mut()?;
+        visited_tiles.nonce = visited.nonce.to_le_bytes();
+        visited_tiles.ciphertexts = visited.ciphertexts;
+
         // Update public stats only
         let player_account = &mut ctx.accounts.player_account;
         player_account.tiles_explored += 1;
+        if treasure_claimed {
+            player_account.treasures_found += 1;
+            let game = &mut ctx.accounts.game;
+            game.treasures_remaining = game.treasures_remaining.saturating_sub(1);
+        }
+        if trap_triggered {
+            player_account.traps_triggered += 1;
+        }
+        if eliminated {
+            player_account.eliminated = true;
+            emit!(PlayerEliminated {
+                game: ctx.accounts.game.key(),
+                player: player_account.wallet,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
 
         // Emit encrypted result — only the player can decrypt
         emit!(ExploreResultEvent {
             game: ctx.accounts.game.key(),
             player: player_account.wallet,
-            encrypted_result: result,
+            encrypted_result: shared_output_bytes(&result),
         });
 
+        finish_action(
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.computation_account,
+            None,
+        )?;
+
         Ok(())
     }
 
-    pub fn dig_callback(ctx: Context<DigCallback>, result: Vec<u8>) -> Result<()> {
+    #[arcium_callback(encrypted_ix = "dig")]
+    pub fn dig_callback(
+        ctx: Context<DigCallback>,
+        output: SignedComputationOutputs<DigOutput>,
+    ) -> Result<()> {
+        let Some(DigOutput {
+            field_0: DigOutputStruct0 {
+                field_0: result,
+                field_1: state,
+                field_2: map,
+                field_3: layer,
+                field_4: treasure_claimed,
+                field_5: trap_triggered,
+                field_6: eliminated,
+            },
+        }) = player_output(
+            output,
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+            &ctx.accounts.instructions_sysvar,
+        )?
+        else {
+            return Ok(());
+        };
+
+        let mut player_state = ctx.accounts.encrypted_player_state.load_mut()?;
+        player_state.nonce = state.nonce.to_le_bytes();
+        player_state.ciphertexts = state.ciphertexts;
+        let mut encrypted_map = ctx.accounts.encrypted_map.load_mut()?;
+        encrypted_map.nonce = map.nonce.to_le_bytes();
+        encrypted_map.ciphertexts = map.ciphertexts;
+        let mut buried_layer = ctx.accounts.encrypted_buried_layer.load_mut()?;
+        buried_layer.nonce = layer.nonce.to_le_bytes();
+        buried_layer.ciphertexts = layer.ciphertexts;
+
         let player_account = &mut ctx.accounts.player_account;
         player_account.tiles_explored += 1;
+        if treasure_claimed {
+            player_account.treasures_found += 1;
+            let game = &mut ctx.accounts.game;
+            game.treasures_remaining = game.treasures_remaining.saturating_sub(1);
+        }
+        if trap_triggered {
+            player_account.traps_triggered += 1;
+        }
+        if eliminated {
+            player_account.eliminated = true;
+            emit!(PlayerEliminated {
+                game: ctx.accounts.game.key(),
+                player: player_account.wallet,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
 
         emit!(DigResultEvent {
             game: ctx.accounts.game.key(),
             player: player_account.wallet,
-            encrypted_result: result,
+            encrypted_result: shared_output_bytes(&result),
         });
 
+        finish_action(
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.computation_account,
+            None,
+        )?;
+
         Ok(())
     }
 
-    pub fn move_callback(ctx: Context<MoveCallback>, result: Vec<u8>) -> Result<()> {
+    #[arcium_callback(encrypted_ix = "move_player")]
+    pub fn move_callback(
+        ctx: Context<MoveCallback>,
+        output: SignedComputationOutputs<MovePlayerOutput>,
+    ) -> Result<()> {
+        let Some(MovePlayerOutput {
+            field_0: MovePlayerOutputStruct0 {
+                field_0: result,
+                field_1: state,
+            },
+        }) = player_output(
+            output,
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+            &ctx.accounts.instructions_sysvar,
+        )?
+        else {
+            return Ok(());
+        };
+
+        let mut player_state = ctx.accounts.encrypted_player_state.load_mut()?;
+        player_state.nonce = state.nonce.to_le_bytes();
+        player_state.ciphertexts = state.ciphertexts;
+
         emit!(MoveResultEvent {
             game: ctx.accounts.game.key(),
             player: ctx.accounts.player_account.wallet,
-            encrypted_result: result,
+            encrypted_result: shared_output_bytes(&result),
         });
 
+        finish_action(
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.computation_account,
+            None,
+        )?;
+
         Ok(())
     }
 
-    pub fn bury_callback(ctx: Context<BuryCallback>, result: Vec<u8>) -> Result<()> {
+    #[arcium_callback(encrypted_ix = "bury")]
+    pub fn bury_callback(
+        ctx: Context<BuryCallback>,
+        output: SignedComputationOutputs<BuryOutput>,
+    ) -> Result<()> {
+        let Some(BuryOutput {
+            field_0: BuryOutputStruct0 {
+                field_0: result,
+                field_1: state,
+                field_2: layer,
+            },
+        }) = player_output(
+            output,
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+            &ctx.accounts.instructions_sysvar,
+        )?
+        else {
+            return Ok(());
+        };
+
+        let mut player_state = ctx.accounts.encrypted_player_state.load_mut()?;
+        player_state.nonce = state.nonce.to_le_bytes();
+        player_state.ciphertexts = state.ciphertexts;
+        let mut buried_layer = ctx.accounts.encrypted_buried_layer.load_mut()?;
+        buried_layer.nonce = layer.nonce.to_le_bytes();
+        buried_layer.ciphertexts = layer.ciphertexts;
+
         emit!(BuryResultEvent {
             game: ctx.accounts.game.key(),
             // NOTE: No tile info in the event — privacy preserved
-            encrypted_result: result,
+            encrypted_result: shared_output_bytes(&result),
         });
 
+        finish_action(
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.computation_account,
+            None,
+        )?;
+
         Ok(())
     }
-}
 
-// =========================================================
-// ACCOUNTS
-// =========================================================
+    #[arcium_callback(encrypted_ix = "descend")]
+    pub fn descend_callback(
+        ctx: Context<DescendCallback>,
+        output: SignedComputationOutputs<DescendOutput>,
+    ) -> Result<()> {
+        let Some(DescendOutput {
+            field_0: DescendOutputStruct0 {
+                field_0: result,
+                field_1: state,
+                field_2: visited,
+                field_3: descended,
+            },
+        }) = player_output(
+            output,
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+            &ctx.accounts.instructions_sysvar,
+        )?
+        else {
+            return Ok(());
+        };
 
-#[account]
-pub struct Game {
+        let mut player_state = ctx.accounts.encrypted_player_state.load_mut()?;
+        player_state.nonce = state.nonce.to_le_bytes();
+        player_state.ciphertexts = state.ciphertexts;
+        let mut visited_tiles = ctx.accounts.encrypted_visited_tiles.load_mut()?;
+        visited_tiles.nonce = visited.nonce.to_le_bytes();
+        visited_tiles.ciphertexts = visited.ciphertexts;
+
+        // The public level routes the player's later actions to that level
+        let player_account = &mut ctx.accounts.player_account;
+        if descended {
+            player_account.level += 1;
+            emit!(PlayerDescended {
+                game: ctx.accounts.game.key(),
+                player: player_account.wallet,
+                level: player_account.level,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+
+        emit!(DescendResultEvent {
+            game: ctx.accounts.game.key(),
+            player: player_account.wallet,
+            encrypted_result: shared_output_bytes(&result),
+        });
+
+        finish_action(
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.computation_account,
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "use_item")]
+    pub fn use_item_callback(
+        ctx: Context<UseItemCallback>,
+        output: SignedComputationOutputs<UseItemOutput>,
+    ) -> Result<()> {
+        let Some(UseItemOutput {
+            field_0: UseItemOutputStruct0 {
+                field_0: result,
+                field_1: state,
+                field_2: map,
+            },
+        }) = player_output(
+            output,
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+            &ctx.accounts.instructions_sysvar,
+        )?
+        else {
+            return Ok(());
+        };
+
+        let mut player_state = ctx.accounts.encrypted_player_state.load_mut()?;
+        player_state.nonce = state.nonce.to_le_bytes();
+        player_state.ciphertexts = state.ciphertexts;
+        let mut encrypted_map = ctx.accounts.encrypted_map.load_mut()?;
+        encrypted_map.nonce = map.nonce.to_le_bytes();
+        encrypted_map.ciphertexts = map.ciphertexts;
+
+        emit!(UseItemResultEvent {
+            game: ctx.accounts.game.key(),
+            player: ctx.accounts.player_account.wallet,
+            encrypted_result: shared_output_bytes(&result),
+        });
+
+        finish_action(
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.computation_account,
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "reveal_standings")]
+    pub fn reveal_standings_callback(
+        ctx: Context<RevealStandingsCallback>,
+        output: SignedComputationOutputs<RevealStandingsOutput>,
+    ) -> Result<()> {
+        let Some(RevealStandingsOutput { field_0: gold }) = player_output(
+            output,
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+            &ctx.accounts.instructions_sysvar,
+        )?
+        else {
+            // Nothing was revealed; the crank can queue the standing again
+            return Ok(());
+        };
+
+        finish_action(
+            &ctx.accounts.game,
+            &mut ctx.accounts.player_account,
+            &ctx.accounts.computation_account,
+            None,
+        )?;
+        ctx.accounts.player_account.standing_revealed = true;
+        let wallet = ctx.accounts.player_account.wallet;
+        record_standing(&ctx.accounts.game, &mut ctx.accounts.game_result, wallet, gold)
+    }
+}
+
+/// Add a revealed standing to the result; the last one resolves the game
+fn record_standing(
+    game: &Account<Game>,
+    result: &mut Account<GameResult>,
+    wallet: Pubkey,
+    gold: u16,
+) -> Result<()> {
+    require!(
+        result.standings.iter().all(|standing| standing.wallet != wallet),
+        GameError::StandingAlreadyRevealed
+    );
+    result.standings.push(Standing { wallet, gold });
+    result.revealed_count += 1;
+    // Ties keep the earlier-revealed player
+    if result.winner == Pubkey::default() || gold > result.winning_gold {
+        result.winner = wallet;
+        result.winning_gold = gold;
+    }
+
+    if result.revealed_count == game.player_count {
+        emit!(GameResolved {
+            game: game.key(),
+            winner: result.winner,
+            winning_gold: result.winning_gold,
+        });
+    }
+
+    Ok(())
+}
+
+// =========================================================
+// COMPUTATION I/O HELPERS
+// =========================================================
+
+/// `requester: Shared` parameter — re-encrypt results to the caller's key
+fn requester_arguments(pub_key: [u8; 32], nonce: u128) -> [Argument; 2] {
+    [Argument::ArcisPubkey(pub_key), Argument::PlaintextU128(nonce)]
+}
+
+/// Check the MXE cluster's signature over a computation's output.
+/// Forged callbacks (wrong cluster, wrong computation, tampered bytes) and
+/// aborted computations both fail here.
+fn verify_output<T>(
+    output: SignedComputationOutputs<T>,
+    cluster_account: &Account<Cluster>,
+    computation_account: &UncheckedAccount,
+) -> Result<T> {
+    output
+        .verify_output(cluster_account, computation_account)
+        .map_err(|_| GameError::InvalidComputationOutput.into())
+}
+
+/// `verify_output` for callbacks that also accept the cluster's report
+/// that the computation failed, returned as the reason it failed. Failure
+/// reports carry no output to sign, so they are only trusted when the
+/// Arcium program itself invoked the callback.
+fn verify_action_output<T>(
+    output: SignedComputationOutputs<T>,
+    cluster_account: &Account<Cluster>,
+    computation_account: &UncheckedAccount,
+    instructions_sysvar: &AccountInfo,
+) -> Result<std::result::Result<T, FailureReason>> {
+    if let SignedComputationOutputs::Failure = output {
+        let caller = get_instruction_relative(0, instructions_sysvar)?;
+        require_keys_eq!(
+            caller.program_id,
+            ARCIUM_PROG_ID,
+            GameError::InvalidComputationOutput
+        );
+        return Ok(Err(FailureReason::Aborted));
+    }
+    verify_output(output, cluster_account, computation_account).map(Ok)
+}
+
+/// `verify_action_output` for a player's callback. A failed computation
+/// releases the player's pending action with ComputationFailed and yields
+/// `None`, leaving the callback nothing to do but return.
+fn player_output<T>(
+    output: SignedComputationOutputs<T>,
+    game: &Account<Game>,
+    player_account: &mut PlayerAccount,
+    cluster_account: &Account<Cluster>,
+    computation_account: &UncheckedAccount,
+    instructions_sysvar: &AccountInfo,
+) -> Result<Option<T>> {
+    match verify_action_output(output, cluster_account, computation_account, instructions_sysvar)? {
+        Ok(output) => Ok(Some(output)),
+        Err(reason) => {
+            finish_action(game, player_account, computation_account, Some(reason))?;
+            Ok(None)
+        }
+    }
+}
+
+/// Serialize an `Enc<Shared, T>` output for events: encryption key + nonce + ciphertexts
+fn shared_output_bytes<const N: usize>(output: &SharedEncryptedStruct<N>) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(32 + 16 + N * 32);
+    bytes.extend_from_slice(&output.encryption_key);
+    bytes.extend_from_slice(&output.nonce.to_le_bytes());
+    for ciphertext in output.ciphertexts.iter() {
+        bytes.extend_from_slice(ciphertext);
+    }
+    bytes
+}
+
+// =========================================================
+// ACCOUNTS
+// =========================================================
+
+#[account]
+pub struct Game {
     pub authority: Pubkey,
+    pub game_id: u64,
     pub player_count: u32,
     pub created_at: i64,
+    /// created_at + `duration` seconds (at most MAX_GAME_DURATION). 0 = no
+    /// deadline: the game ends by authority or once every treasure is claimed
+    pub ends_at: i64,
+    pub treasures_remaining: u32, // on levels whose map has landed
+    pub levels_ready: u8,         // levels whose map has landed
+    pub config: GameConfig,
+    pub economics: Economics,
+    pub cooldown: CooldownConfig,
+    /// 0 = free-for-all. Otherwise turn mode: players act one at a time in
+    /// registration order, each with this many seconds (at most
+    /// MAX_TURN_DURATION) before anyone may skip_turn them
+    pub turn_duration: i64,
+    pub turn_seat: u32,     // seat whose turn it is
+    pub turn_started_at: i64,
+    pub mint: Pubkey,   // default = no stakes
+    pub entry_fee: u64, // per player, in `mint` base units
+    pub action_sequence: u64, // computations queued so far
     pub is_active: bool,
+    pub bump: u8,
+}
+
+impl Game {
+    /// Accepting actions: not ended and deadline (if any) not yet reached
+    pub fn is_running(&self, now: i64) -> bool {
+        self.is_active && (self.ends_at == 0 || now < self.ends_at)
+    }
+
+    /// Players pay an entry fee into the vault
+    pub fn is_staked(&self) -> bool {
+        self.mint != Pubkey::default()
+    }
+}
+
+/// In turn mode, only the seat whose turn it is may act, and acting
+/// passes the turn on. A no-op otherwise.
+fn take_turn(game: &mut Account<Game>, seat: u32, now: i64) -> Result<()> {
+    if game.turn_duration == 0 {
+        return Ok(());
+    }
+    require!(seat == game.turn_seat, GameError::NotYourTurn);
+    advance_turn(game, now, false);
+    Ok(())
+}
+
+/// Round-robin to the next seat and restart the turn clock
+fn advance_turn(game: &mut Account<Game>, now: i64, skipped: bool) {
+    game.turn_seat = (game.turn_seat + 1) % game.player_count;
+    game.turn_started_at = now;
+    emit!(TurnAdvanced {
+        game: game.key(),
+        seat: game.turn_seat,
+        deadline: now + game.turn_duration,
+        skipped,
+    });
+}
+
+/// Number a player action and announce the computation it queued. The
+/// player account holds it as pending until its callback lands, which
+/// keeps a second action from racing it on the same encrypted state.
+fn record_action(
+    game: &mut Account<Game>,
+    player_account: &mut PlayerAccount,
+    action_type: ActionType,
+    computation_offset: u64,
+    now: i64,
+) {
+    game.action_sequence += 1;
+    player_account.pending_computation = Some(computation_offset);
+    player_account.pending_sequence = game.action_sequence;
+    player_account.pending_since = now;
+
+    emit!(ActionPerformed {
+        game: game.key(),
+        player: player_account.wallet,
+        action_type,
+        sequence: game.action_sequence,
+        timestamp: now,
+    });
+    emit!(ComputationQueued {
+        game: game.key(),
+        computation_offset,
+        sequence: game.action_sequence,
+    });
+}
+
+/// Number a computation that is not a player action — level setup or a
+/// standing reveal — in the game's sequence and report it queued
+fn sequence_computation(game: &mut Account<Game>, computation_offset: u64) -> u64 {
+    game.action_sequence += 1;
+    emit!(ComputationQueued {
+        game: game.key(),
+        computation_offset,
+        sequence: game.action_sequence,
+    });
+    game.action_sequence
+}
+
+/// Release the player's pending computation from its callback. Rejects
+/// outputs for anything but the computation in flight — e.g. one already
+/// released by abort_pending, or a replay of one already finished.
+fn take_pending(
+    player_account: &mut PlayerAccount,
+    computation_account: &UncheckedAccount,
+) -> Result<u64> {
+    let computation_offset = player_account
+        .pending_computation
+        .take()
+        .ok_or(GameError::NoPendingComputation)?;
+    require_keys_eq!(
+        computation_account.key(),
+        derive_comp_pda!(computation_offset),
+        GameError::NoPendingComputation
+    );
+    Ok(computation_offset)
+}
+
+/// Release the player's pending action and report how it ended:
+/// finalized, or failed for `failure`
+fn finish_action(
+    game: &Account<Game>,
+    player_account: &mut PlayerAccount,
+    computation_account: &UncheckedAccount,
+    failure: Option<FailureReason>,
+) -> Result<()> {
+    let computation_offset = take_pending(player_account, computation_account)?;
+
+    let sequence = player_account.pending_sequence;
+    match failure {
+        None => emit!(ComputationFinalized {
+            game: game.key(),
+            computation_offset,
+            sequence,
+        }),
+        Some(reason) => emit!(ComputationFailed {
+            game: game.key(),
+            computation_offset,
+            sequence,
+            reason,
+        }),
+    }
+    Ok(())
+}
+
+/// Report how an init_map or init_buried_layer ended, as finish_action
+/// does for a player's action
+fn finish_init(
+    game: &Account<Game>,
+    computation_offset: u64,
+    sequence: u64,
+    failure: Option<FailureReason>,
+) -> Result<()> {
+    match failure {
+        None => emit!(ComputationFinalized {
+            game: game.key(),
+            computation_offset,
+            sequence,
+        }),
+        Some(reason) => emit!(ComputationFailed {
+            game: game.key(),
+            computation_offset,
+            sequence,
+            reason,
+        }),
+    }
+    Ok(())
+}
+
+/// Transfer out of the token vault, signed by the game PDA that owns it
+fn pay_from_vault<'info>(
+    game: &Account<'info, Game>,
+    vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let game_id = game.game_id.to_le_bytes();
+    let seeds: &[&[u8]] = &[b"game", game.authority.as_ref(), &game_id, &[game.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: to.to_account_info(),
+                authority: game.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )
 }
 
 #[account]
@@ -343,149 +2071,1833 @@ pub struct PlayerAccount {
     pub tiles_explored: u32,
     pub treasures_found: u32,
     pub traps_triggered: u32,
+    pub eliminated: bool, // Revealed by MPC when health reaches 0
+    pub standing_revealed: bool,
+    pub winnings_claimed: bool,
+    pub seat: u32, // registration order, for turn mode
+    pub last_action_slot: u64,
+    pub action_points: u16, // as of last_action_slot
+    pub pending_computation: Option<u64>, // offset of the action in flight
+    pub pending_sequence: u64,
+    pub pending_since: i64,
+    pub level: u8, // dungeon level, public so actions reach its map
+    pub bump: u8,
+}
+
+impl PlayerAccount {
+    /// Top up the action points regenerated since the last action, then
+    /// spend one. Regeneration restarts from every action, so a spammer
+    /// never banks partial points. A no-op if the game has no cooldown.
+    fn spend_action_point(&mut self, cooldown: &CooldownConfig, slot: u64) -> Result<()> {
+        if cooldown.max_action_points > 0 {
+            let regenerated = slot.saturating_sub(self.last_action_slot) / cooldown.slots_per_point;
+            let points = (self.action_points as u64 + regenerated).min(cooldown.max_action_points as u64);
+            require!(points > 0, GameError::Cooldown);
+            self.action_points = points as u16 - 1;
+        }
+        self.last_action_slot = slot;
+        Ok(())
+    }
+}
+
+/// Public map parameters chosen at create_game (mirrors `MapConfig` in the circuits)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct GameConfig {
+    pub width: u8,
+    pub height: u8,
+    pub treasure_count: u8,
+    pub trap_count: u8,
+    pub treasure_min: u16,
+    pub treasure_max: u16,
+    pub trap_min: u16,
+    pub trap_max: u16,
+    pub levels: u8, // 1 = a single map, up to MAX_LEVELS
+    pub wall_count: u8,
+    pub spring_count: u8,
+    pub portal_pairs: u8, // up to MAX_PORTAL_PAIRS
+    pub item_count: u8,
+}
+
+impl GameConfig {
+    pub const SIZE: usize = 1 + 1 + 1 + 1 + 2 + 2 + 2 + 2 + 1 + 1 + 1 + 1 + 1;
+
+    /// Reject configs init_map could not satisfy — every treasure, trap,
+    /// staircase, wall, spring, portal and item cache needs its own
+    /// non-spawn tile — or that leave nothing to find
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            MAP_TIERS.contains(&(self.width, self.height)),
+            GameError::UnsupportedMapSize
+        );
+        require!(self.treasure_count > 0, GameError::InvalidGameConfig);
+        require!(
+            self.levels > 0 && self.levels <= MAX_LEVELS,
+            GameError::InvalidGameConfig
+        );
+        require!(
+            self.portal_pairs <= MAX_PORTAL_PAIRS,
+            GameError::InvalidGameConfig
+        );
+        let tiles = self.width as usize * self.height as usize;
+        let stairs = (self.levels > 1) as usize;
+        let placed = self.treasure_count as usize
+            + self.trap_count as usize
+            + stairs
+            + self.wall_count as usize
+            + self.spring_count as usize
+            + 2 * self.portal_pairs as usize
+            + self.item_count as usize;
+        require!(placed < tiles, GameError::InvalidGameConfig);
+        require!(
+            self.treasure_min > 0 && self.treasure_min <= self.treasure_max,
+            GameError::InvalidGameConfig
+        );
+        require!(
+            self.trap_min > 0 && self.trap_min <= self.trap_max,
+            GameError::InvalidGameConfig
+        );
+        Ok(())
+    }
+
+    /// `cfg: MapConfig` plaintext parameter, field by field
+    fn as_arguments(&self) -> [Argument; 13] {
+        [
+            Argument::PlaintextU8(self.width),
+            Argument::PlaintextU8(self.height),
+            Argument::PlaintextU8(self.treasure_count),
+            Argument::PlaintextU8(self.trap_count),
+            Argument::PlaintextU16(self.treasure_min),
+            Argument::PlaintextU16(self.treasure_max),
+            Argument::PlaintextU16(self.trap_min),
+            Argument::PlaintextU16(self.trap_max),
+            Argument::PlaintextU8(self.levels),
+            Argument::PlaintextU8(self.wall_count),
+            Argument::PlaintextU8(self.spring_count),
+            Argument::PlaintextU8(self.portal_pairs),
+            Argument::PlaintextU8(self.item_count),
+        ]
+    }
+}
+
+/// Public economic parameters chosen at create_game
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Economics {
+    pub entry_fee_lamports: u64, // 0 = free to join
+    pub rake_bps: u16,           // authority's cut of every pool
+    pub max_players: u32,        // 0 = unlimited
+    pub payout_curve: PayoutCurve,
+}
+
+impl Economics {
+    pub const SIZE: usize = 8 + 2 + 4 + 1;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(self.rake_bps <= MAX_RAKE_BPS, GameError::InvalidEconomics);
+        Ok(())
+    }
+}
+
+/// Per-player action rate limit chosen at create_game: a bucket of
+/// `max_action_points` that refills one point every `slots_per_point` slots
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct CooldownConfig {
+    pub max_action_points: u16, // 0 = unlimited
+    pub slots_per_point: u64,
+}
+
+impl CooldownConfig {
+    pub const SIZE: usize = 2 + 8;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.max_action_points == 0 || self.slots_per_point > 0,
+            GameError::InvalidGameConfig
+        );
+        Ok(())
+    }
+}
+
+/// Final standings, seeds = [b"result", game]. Filled in one player at a
+/// time by reveal_standings_callback after the game ends.
+#[account]
+pub struct GameResult {
+    pub game: Pubkey,
+    pub winner: Pubkey,
+    pub winning_gold: u16,
+    pub revealed_count: u32,
+    pub claimed_count: u32,
+    pub standings: Vec<Standing>,
+    pub lamport_pool: Pool,
+    pub token_pool: Pool, // empty if unstaked
+    pub rake_withdrawn: bool,
+    pub bump: u8,
+}
+
+impl GameResult {
+    /// A player's weight under `curve`, and the weight of the whole field.
+    /// Ties rank by reveal order, as for the winner.
+    fn payout_weight(&self, curve: PayoutCurve, wallet: &Pubkey) -> Result<(u64, u64)> {
+        let gold = self
+            .standings
+            .iter()
+            .find(|standing| standing.wallet == *wallet)
+            .map(|standing| standing.gold as u64)
+            .ok_or(GameError::StandingsPending)?;
+
+        Ok(match curve {
+            PayoutCurve::Proportional => {
+                let total_gold = self.standings.iter().map(|standing| standing.gold as u64).sum();
+                // Nobody finished with gold — split evenly
+                if total_gold == 0 {
+                    (1, self.standings.len() as u64)
+                } else {
+                    (gold, total_gold)
+                }
+            }
+            PayoutCurve::WinnerTakesAll => ((*wallet == self.winner) as u64, 1),
+            PayoutCurve::TopThree => {
+                let mut ranked: Vec<&Standing> = self.standings.iter().collect();
+                ranked.sort_by(|a, b| b.gold.cmp(&a.gold)); // stable: keeps reveal order
+                let places = &TOP_THREE_WEIGHTS[..ranked.len().min(TOP_THREE_WEIGHTS.len())];
+                let rank = ranked.iter().position(|standing| standing.wallet == *wallet);
+                let weight = rank.and_then(|rank| places.get(rank)).copied().unwrap_or(0);
+                (weight, places.iter().sum())
+            }
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Standing {
+    pub wallet: Pubkey,
+    pub gold: u16,
+}
+
+/// One prize pool, fixed at end_game
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Pool {
+    pub total: u64, // entry fee * player_count
+    pub rake: u64,
+    pub paid_out: u64, // claims, plus the rake once withdrawn
+}
+
+impl Pool {
+    pub const SIZE: usize = 8 + 8 + 8;
+
+    fn new(total: u64, rake_bps: u16) -> Self {
+        Pool {
+            total,
+            rake: (total as u128 * rake_bps as u128 / BPS_DENOMINATOR as u128) as u64,
+            paid_out: 0,
+        }
+    }
+
+    /// `weight / total_weight` of what players split
+    fn share(&self, weight: u64, total_weight: u64) -> u64 {
+        ((self.total - self.rake) as u128 * weight as u128 / total_weight as u128) as u64
+    }
+
+    /// What a claim pays: its share, or on the last claim everything left
+    /// for players, rounding dust included
+    fn payout(&self, weight: u64, total_weight: u64, last: bool, rake_withdrawn: bool) -> u64 {
+        if !last {
+            return self.share(weight, total_weight);
+        }
+        let rake_held = if rake_withdrawn { 0 } else { self.rake };
+        self.outstanding() - rake_held
+    }
+
+    fn outstanding(&self) -> u64 {
+        self.total - self.paid_out
+    }
+}
+
+// Encrypted state PDAs — ciphertexts written by callbacks and passed back
+// to later computations by reference. Zero-copy so the large layers are
+// never borsh-deserialized onto the stack.
+
+/// `Enc<Mxe, MapMatrix>` for one level, seeds = [b"map", game, level]
+#[account(zero_copy)]
+pub struct EncryptedMap {
+    pub game: Pubkey,
+    pub nonce: [u8; 16],
+    pub ciphertexts: [[u8; 32]; MAP_CIPHERTEXTS],
+    pub bump: u8,
+    pub level: u8,
+    pub pending_computation: [u8; 8], // offset of the init_map that fills it
+    pub pending_sequence: [u8; 8],    // its ComputationQueued sequence
+    pub initialized: u8,              // 1 once that callback has landed
+}
+
+/// `Enc<Mxe, BuriedLayer>` for one level, seeds = [b"buried", game, level]
+#[account(zero_copy)]
+pub struct EncryptedBuriedLayer {
+    pub game: Pubkey,
+    pub nonce: [u8; 16],
+    pub ciphertexts: [[u8; 32]; BURIED_CIPHERTEXTS],
+    pub bump: u8,
+    pub level: u8,
+    pub pending_computation: [u8; 8], // offset of the init_buried_layer that fills it
+    pub pending_sequence: [u8; 8],    // its ComputationQueued sequence
+    pub initialized: u8,              // 1 once that callback has landed
+}
+
+/// `Enc<Mxe, PlayerState>` for one player, seeds = [b"player_state", game, wallet]
+#[account(zero_copy)]
+pub struct EncryptedPlayerState {
+    pub game: Pubkey,
+    pub wallet: Pubkey,
+    pub nonce: [u8; 16],
+    pub ciphertexts: [[u8; 32]; PLAYER_STATE_CIPHERTEXTS],
+    pub bump: u8,
+    pub initialized: u8, // 1 once register_player's callback has landed
+}
+
+/// `Enc<Mxe, VisitedTiles>` for one player, seeds = [b"visited", game, wallet]
+#[account(zero_copy)]
+pub struct EncryptedVisitedTiles {
+    pub game: Pubkey,
+    pub wallet: Pubkey,
+    pub nonce: [u8; 16],
+    pub ciphertexts: [[u8; 32]; VISITED_CIPHERTEXTS],
+    pub bump: u8,
+}
+
+impl EncryptedMap {
+    const CIPHERTEXTS_OFFSET: u32 = 8 + 32 + 16;
+
+    /// `Enc<Mxe, MapMatrix>` parameter — nonce plus an account reference
+    fn as_arguments(&self, key: Pubkey) -> [Argument; 2] {
+        [
+            Argument::PlaintextU128(u128::from_le_bytes(self.nonce)),
+            Argument::Account(key, Self::CIPHERTEXTS_OFFSET, (MAP_CIPHERTEXTS * 32) as u32),
+        ]
+    }
+
+    /// The init_map computation queued for this map, if `computation_account`
+    /// is it and its output has not landed yet. Accepting it only once
+    /// keeps a replay from restoring every claimed treasure.
+    fn pending_init(&self, computation_account: &UncheckedAccount) -> Result<u64> {
+        let computation_offset = u64::from_le_bytes(self.pending_computation);
+        require!(self.initialized == 0, GameError::NoPendingComputation);
+        require_keys_eq!(
+            computation_account.key(),
+            derive_comp_pda!(computation_offset),
+            GameError::NoPendingComputation
+        );
+        Ok(computation_offset)
+    }
+}
+
+impl EncryptedBuriedLayer {
+    const CIPHERTEXTS_OFFSET: u32 = 8 + 32 + 16;
+
+    /// `Enc<Mxe, BuriedLayer>` parameter — nonce plus an account reference
+    fn as_arguments(&self, key: Pubkey) -> [Argument; 2] {
+        [
+            Argument::PlaintextU128(u128::from_le_bytes(self.nonce)),
+            Argument::Account(key, Self::CIPHERTEXTS_OFFSET, (BURIED_CIPHERTEXTS * 32) as u32),
+        ]
+    }
+
+    /// The init_buried_layer computation queued for this layer, if
+    /// `computation_account` is it and its output has not landed yet.
+    /// Accepting it only once keeps a replay from wiping all buried loot.
+    fn pending_init(&self, computation_account: &UncheckedAccount) -> Result<u64> {
+        let computation_offset = u64::from_le_bytes(self.pending_computation);
+        require!(self.initialized == 0, GameError::NoPendingComputation);
+        require_keys_eq!(
+            computation_account.key(),
+            derive_comp_pda!(computation_offset),
+            GameError::NoPendingComputation
+        );
+        Ok(computation_offset)
+    }
+}
+
+impl EncryptedPlayerState {
+    const CIPHERTEXTS_OFFSET: u32 = 8 + 32 + 32 + 16;
+
+    /// `Enc<Mxe, PlayerState>` parameter — nonce plus an account reference
+    fn as_arguments(&self, key: Pubkey) -> [Argument; 2] {
+        [
+            Argument::PlaintextU128(u128::from_le_bytes(self.nonce)),
+            Argument::Account(
+                key,
+                Self::CIPHERTEXTS_OFFSET,
+                (PLAYER_STATE_CIPHERTEXTS * 32) as u32,
+            ),
+        ]
+    }
+}
+
+impl EncryptedVisitedTiles {
+    const CIPHERTEXTS_OFFSET: u32 = 8 + 32 + 32 + 16;
+
+    /// `Enc<Mxe, VisitedTiles>` parameter — nonce plus an account reference
+    fn as_arguments(&self, key: Pubkey) -> [Argument; 2] {
+        [
+            Argument::PlaintextU128(u128::from_le_bytes(self.nonce)),
+            Argument::Account(key, Self::CIPHERTEXTS_OFFSET, (VISITED_CIPHERTEXTS * 32) as u32),
+        ]
+    }
 }
 
 // =========================================================
-// ACCOUNT CONTEXTS (simplified — real impl has full Arcium PDAs)
+// ACCOUNT CONTEXTS
 // =========================================================
 
+#[queue_computation_accounts("init_map", authority)]
 #[derive(Accounts)]
+#[instruction(computation_offset: u64, game_id: u64)]
 pub struct CreateGame<'info> {
-    #[account(init, payer = authority, space = 8 + 32 + 4 + 8 + 1)]
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 8 + 4 + 8 + 8 + 4 + 1 + GameConfig::SIZE + Economics::SIZE + CooldownConfig::SIZE + 8 + 4 + 8 + 32 + 8 + 8 + 1 + 1,
+        seeds = [b"game", authority.key().as_ref(), &game_id.to_le_bytes()],
+        bump,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<EncryptedMap>(),
+        seeds = [b"map", game.key().as_ref(), &[0]],
+        bump,
+    )]
+    pub encrypted_map: AccountLoader<'info, EncryptedMap>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = authority,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_MAP))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[queue_computation_accounts("init_map", authority)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, level: u8)]
+pub struct InitLevel<'info> {
+    #[account(mut, has_one = authority @ GameError::Unauthorized)]
+    pub game: Account<'info, Game>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<EncryptedMap>(),
+        seeds = [b"map", game.key().as_ref(), &[level]],
+        bump,
+    )]
+    pub encrypted_map: AccountLoader<'info, EncryptedMap>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = authority,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_MAP))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[queue_computation_accounts("init_map", authority)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, level: u8)]
+pub struct RetryLevelInit<'info> {
+    #[account(mut, has_one = authority @ GameError::Unauthorized)]
     pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"map", game.key().as_ref(), &[level]],
+        bump = encrypted_map.load()?.bump,
+        constraint = encrypted_map.load()?.initialized == 0 @ GameError::LevelAlreadyReady,
+    )]
+    pub encrypted_map: AccountLoader<'info, EncryptedMap>,
     #[account(mut)]
     pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = authority,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_MAP))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
     pub system_program: Program<'info, System>,
-    // ... Arcium required accounts (MXE, cluster, computation, mempool, etc.)
+    pub arcium_program: Program<'info, Arcium>,
 }
 
+#[queue_computation_accounts("register_player", player)]
 #[derive(Accounts)]
+#[instruction(computation_offset: u64)]
 pub struct RegisterPlayer<'info> {
-    #[account(init, payer = player, space = 8 + 32 + 32 + 8 + 4 + 4 + 4)]
+    // init_if_needed so a second registration reaches the handler and fails
+    // with PlayerAlreadyRegistered, not the system program's "already in use"
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + 32 + 32 + 8 + 4 + 4 + 4 + 1 + 1 + 1 + 4 + 8 + 2 + 9 + 8 + 8 + 1 + 1,
+        seeds = [b"player", game.key().as_ref(), player.key().as_ref()],
+        bump,
+    )]
     pub player_account: Account<'info, PlayerAccount>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + std::mem::size_of::<EncryptedPlayerState>(),
+        seeds = [b"player_state", game.key().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + std::mem::size_of::<EncryptedVisitedTiles>(),
+        seeds = [b"visited", game.key().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub encrypted_visited_tiles: AccountLoader<'info, EncryptedVisitedTiles>,
     #[account(mut)]
     pub game: Account<'info, Game>,
     #[account(mut)]
     pub player: Signer<'info>,
+    // Staked games only — entry fee source and destination
+    #[account(
+        mut,
+        token::mint = game.mint,
+        token::authority = player,
+    )]
+    pub player_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"vault", game.key().as_ref()],
+        bump,
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = player,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REGISTER_PLAYER))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
     pub system_program: Program<'info, System>,
-    // ... Arcium required accounts
+    pub arcium_program: Program<'info, Arcium>,
 }
 
+#[queue_computation_accounts("register_player", player)]
 #[derive(Accounts)]
 #[instruction(computation_offset: u64)]
-pub struct MovePlayer<'info> {
+pub struct RetryRegistration<'info> {
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player.key().as_ref()],
+        bump = player_account.bump,
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player.key().as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        mut,
+        seeds = [b"visited", game.key().as_ref(), player.key().as_ref()],
+        bump = encrypted_visited_tiles.load()?.bump,
+    )]
+    pub encrypted_visited_tiles: AccountLoader<'info, EncryptedVisitedTiles>,
     #[account(mut)]
+    pub game: Account<'info, Game>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = player,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REGISTER_PLAYER))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[queue_computation_accounts("move_player", player)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct MovePlayer<'info> {
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player.key().as_ref()],
+        bump = player_account.bump,
+    )]
     pub player_account: Account<'info, PlayerAccount>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player.key().as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+        constraint = encrypted_player_state.load()?.initialized == 1 @ GameError::PlayerNotReady,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        seeds = [b"map", game.key().as_ref(), &[player_account.level]],
+        bump = encrypted_map.load()?.bump,
+        constraint = encrypted_map.load()?.initialized == 1 @ GameError::LevelNotReady,
+    )]
+    pub encrypted_map: AccountLoader<'info, EncryptedMap>,
+    #[account(mut)]
     pub game: Account<'info, Game>,
+    #[account(mut)]
     pub player: Signer<'info>,
-    // ... Arcium accounts for queue_computation
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = player,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_MOVE_PLAYER))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
 }
 
+#[queue_computation_accounts("explore", player)]
 #[derive(Accounts)]
 #[instruction(computation_offset: u64)]
 pub struct Explore<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player.key().as_ref()],
+        bump = player_account.bump,
+    )]
     pub player_account: Account<'info, PlayerAccount>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player.key().as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+        constraint = encrypted_player_state.load()?.initialized == 1 @ GameError::PlayerNotReady,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        seeds = [b"map", game.key().as_ref(), &[player_account.level]],
+        bump = encrypted_map.load()?.bump,
+        constraint = encrypted_map.load()?.initialized == 1 @ GameError::LevelNotReady,
+    )]
+    pub encrypted_map: AccountLoader<'info, EncryptedMap>,
+    #[account(
+        seeds = [b"visited", game.key().as_ref(), player.key().as_ref()],
+        bump = encrypted_visited_tiles.load()?.bump,
+    )]
+    pub encrypted_visited_tiles: AccountLoader<'info, EncryptedVisitedTiles>,
+    #[account(mut)]
     pub game: Account<'info, Game>,
+    #[account(mut)]
     pub player: Signer<'info>,
-    // ... Arcium accounts
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = player,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_EXPLORE))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
 }
 
+#[queue_computation_accounts("bury", player)]
 #[derive(Accounts)]
 #[instruction(computation_offset: u64)]
 pub struct Bury<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player.key().as_ref()],
+        bump = player_account.bump,
+    )]
     pub player_account: Account<'info, PlayerAccount>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player.key().as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+        constraint = encrypted_player_state.load()?.initialized == 1 @ GameError::PlayerNotReady,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        seeds = [b"buried", game.key().as_ref(), &[player_account.level]],
+        bump = encrypted_buried_layer.load()?.bump,
+        constraint = encrypted_buried_layer.load()?.initialized == 1 @ GameError::LevelNotReady,
+    )]
+    pub encrypted_buried_layer: AccountLoader<'info, EncryptedBuriedLayer>,
+    #[account(mut)]
     pub game: Account<'info, Game>,
+    #[account(mut)]
     pub player: Signer<'info>,
-    // ... Arcium accounts
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = player,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_BURY))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
 }
 
+#[queue_computation_accounts("dig", player)]
 #[derive(Accounts)]
 #[instruction(computation_offset: u64)]
 pub struct Dig<'info> {
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player.key().as_ref()],
+        bump = player_account.bump,
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player.key().as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+        constraint = encrypted_player_state.load()?.initialized == 1 @ GameError::PlayerNotReady,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        seeds = [b"map", game.key().as_ref(), &[player_account.level]],
+        bump = encrypted_map.load()?.bump,
+        constraint = encrypted_map.load()?.initialized == 1 @ GameError::LevelNotReady,
+    )]
+    pub encrypted_map: AccountLoader<'info, EncryptedMap>,
+    #[account(
+        seeds = [b"buried", game.key().as_ref(), &[player_account.level]],
+        bump = encrypted_buried_layer.load()?.bump,
+        constraint = encrypted_buried_layer.load()?.initialized == 1 @ GameError::LevelNotReady,
+    )]
+    pub encrypted_buried_layer: AccountLoader<'info, EncryptedBuriedLayer>,
+    #[account(mut)]
+    pub game: Account<'info, Game>,
     #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = player,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_DIG))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[queue_computation_accounts("descend", player)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct Descend<'info> {
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player.key().as_ref()],
+        bump = player_account.bump,
+    )]
     pub player_account: Account<'info, PlayerAccount>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player.key().as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+        constraint = encrypted_player_state.load()?.initialized == 1 @ GameError::PlayerNotReady,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        seeds = [b"map", game.key().as_ref(), &[player_account.level]],
+        bump = encrypted_map.load()?.bump,
+    )]
+    pub encrypted_map: AccountLoader<'info, EncryptedMap>,
+    #[account(
+        seeds = [b"visited", game.key().as_ref(), player.key().as_ref()],
+        bump = encrypted_visited_tiles.load()?.bump,
+    )]
+    pub encrypted_visited_tiles: AccountLoader<'info, EncryptedVisitedTiles>,
+    // The level below — absent on the deepest level
+    #[account(
+        seeds = [b"map", game.key().as_ref(), &[player_account.level + 1]],
+        bump = next_map.load()?.bump,
+    )]
+    pub next_map: Option<AccountLoader<'info, EncryptedMap>>,
+    #[account(
+        seeds = [b"buried", game.key().as_ref(), &[player_account.level + 1]],
+        bump = next_buried_layer.load()?.bump,
+    )]
+    pub next_buried_layer: Option<AccountLoader<'info, EncryptedBuriedLayer>>,
+    #[account(mut)]
     pub game: Account<'info, Game>,
+    #[account(mut)]
     pub player: Signer<'info>,
-    // ... Arcium accounts
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = player,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_DESCEND))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
 }
 
-// Callback contexts
+#[queue_computation_accounts("use_item", player)]
 #[derive(Accounts)]
-pub struct ExploreCallback<'info> {
+#[instruction(computation_offset: u64)]
+pub struct UseItem<'info> {
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player.key().as_ref()],
+        bump = player_account.bump,
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player.key().as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+        constraint = encrypted_player_state.load()?.initialized == 1 @ GameError::PlayerNotReady,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        seeds = [b"map", game.key().as_ref(), &[player_account.level]],
+        bump = encrypted_map.load()?.bump,
+        constraint = encrypted_map.load()?.initialized == 1 @ GameError::LevelNotReady,
+    )]
+    pub encrypted_map: AccountLoader<'info, EncryptedMap>,
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = player,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_USE_ITEM))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[derive(Accounts)]
+pub struct SkipTurn<'info> {
     #[account(mut)]
+    pub game: Account<'info, Game>,
+    /// Holder of the seat being skipped
+    #[account(
+        constraint = current_player.game == game.key() @ GameError::NotYourTurn,
+        constraint = current_player.seat == game.turn_seat @ GameError::NotYourTurn,
+    )]
+    pub current_player: Account<'info, PlayerAccount>,
+}
+
+#[derive(Accounts)]
+pub struct AbortPending<'info> {
+    /// Holder of the stuck computation
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = player_account.bump,
+    )]
     pub player_account: Account<'info, PlayerAccount>,
     pub game: Account<'info, Game>,
 }
 
 #[derive(Accounts)]
-pub struct DigCallback<'info> {
+pub struct EndGame<'info> {
     #[account(mut)]
+    pub game: Account<'info, Game>,
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + 32 + 32 + 2 + 4 + 4 + 4 + game.player_count as usize * (32 + 2) + Pool::SIZE * 2 + 1 + 1,
+        seeds = [b"result", game.key().as_ref()],
+        bump,
+    )]
+    pub game_result: Account<'info, GameResult>,
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("reveal_standings", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RevealStanding<'info> {
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = player_account.bump,
+    )]
     pub player_account: Account<'info, PlayerAccount>,
+    #[account(
+        seeds = [b"player_state", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+        constraint = encrypted_player_state.load()?.initialized == 1 @ GameError::PlayerNotReady,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(mut)]
     pub game: Account<'info, Game>,
+    #[account(
+        seeds = [b"result", game.key().as_ref()],
+        bump = game_result.bump,
+    )]
+    pub game_result: Account<'info, GameResult>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_STANDINGS))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
 }
 
+#[derive(Accounts)]
+pub struct RevealUnregistered<'info> {
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = player_account.bump,
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    #[account(
+        seeds = [b"player_state", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+        constraint = encrypted_player_state.load()?.initialized == 0 @ GameError::PlayerAlreadyRegistered,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"result", game.key().as_ref()],
+        bump = game_result.bump,
+    )]
+    pub game_result: Account<'info, GameResult>,
+}
+
+// Callback contexts — the Arcium callback account set comes first, followed
+// by the CallbackAccounts registered when the computation was queued
+#[callback_accounts("explore")]
+#[derive(Accounts)]
+pub struct ExploreCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_EXPLORE))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    /// CHECK: bound to the output signature by verify_output
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = player_account.bump,
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        mut,
+        seeds = [b"map", game.key().as_ref(), &[player_account.level]],
+        bump = encrypted_map.load()?.bump,
+    )]
+    pub encrypted_map: AccountLoader<'info, EncryptedMap>,
+    #[account(
+        mut,
+        seeds = [b"visited", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = encrypted_visited_tiles.load()?.bump,
+    )]
+    pub encrypted_visited_tiles: AccountLoader<'info, EncryptedVisitedTiles>,
+}
+
+#[callback_accounts("dig")]
+#[derive(Accounts)]
+pub struct DigCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_DIG))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    /// CHECK: bound to the output signature by verify_output
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = player_account.bump,
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        mut,
+        seeds = [b"map", game.key().as_ref(), &[player_account.level]],
+        bump = encrypted_map.load()?.bump,
+    )]
+    pub encrypted_map: AccountLoader<'info, EncryptedMap>,
+    #[account(
+        mut,
+        seeds = [b"buried", game.key().as_ref(), &[player_account.level]],
+        bump = encrypted_buried_layer.load()?.bump,
+    )]
+    pub encrypted_buried_layer: AccountLoader<'info, EncryptedBuriedLayer>,
+}
+
+#[callback_accounts("move_player")]
 #[derive(Accounts)]
 pub struct MoveCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_MOVE_PLAYER))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    /// CHECK: bound to the output signature by verify_output
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = player_account.bump,
+    )]
     pub player_account: Account<'info, PlayerAccount>,
     pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
 }
 
+#[callback_accounts("bury")]
 #[derive(Accounts)]
 pub struct BuryCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_BURY))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    /// CHECK: bound to the output signature by verify_output
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = player_account.bump,
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        mut,
+        seeds = [b"buried", game.key().as_ref(), &[player_account.level]],
+        bump = encrypted_buried_layer.load()?.bump,
+    )]
+    pub encrypted_buried_layer: AccountLoader<'info, EncryptedBuriedLayer>,
+}
+
+#[callback_accounts("descend")]
+#[derive(Accounts)]
+pub struct DescendCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_DESCEND))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    /// CHECK: bound to the output signature by verify_output
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = player_account.bump,
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        mut,
+        seeds = [b"visited", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = encrypted_visited_tiles.load()?.bump,
+    )]
+    pub encrypted_visited_tiles: AccountLoader<'info, EncryptedVisitedTiles>,
+}
+
+#[callback_accounts("use_item")]
+#[derive(Accounts)]
+pub struct UseItemCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_USE_ITEM))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    /// CHECK: bound to the output signature by verify_output
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = player_account.bump,
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        mut,
+        seeds = [b"map", game.key().as_ref(), &[player_account.level]],
+        bump = encrypted_map.load()?.bump,
+    )]
+    pub encrypted_map: AccountLoader<'info, EncryptedMap>,
+}
+
+#[callback_accounts("init_map")]
+#[derive(Accounts)]
+pub struct InitMapCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_MAP))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    /// CHECK: bound to the output signature by verify_output
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"map", game.key().as_ref(), &[encrypted_map.load()?.level]],
+        bump = encrypted_map.load()?.bump,
+    )]
+    pub encrypted_map: AccountLoader<'info, EncryptedMap>,
+}
+
+#[callback_accounts("init_buried_layer")]
+#[derive(Accounts)]
+pub struct InitBuriedLayerCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_BURIED_LAYER))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    /// CHECK: bound to the output signature by verify_output
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"buried", game.key().as_ref(), &[encrypted_buried_layer.load()?.level]],
+        bump = encrypted_buried_layer.load()?.bump,
+    )]
+    pub encrypted_buried_layer: AccountLoader<'info, EncryptedBuriedLayer>,
+}
+
+#[callback_accounts("register_player")]
+#[derive(Accounts)]
+pub struct RegisterPlayerCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REGISTER_PLAYER))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    /// CHECK: bound to the output signature by verify_output
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = player_account.bump,
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
     pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"player_state", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = encrypted_player_state.load()?.bump,
+    )]
+    pub encrypted_player_state: AccountLoader<'info, EncryptedPlayerState>,
+    #[account(
+        mut,
+        seeds = [b"visited", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = encrypted_visited_tiles.load()?.bump,
+    )]
+    pub encrypted_visited_tiles: AccountLoader<'info, EncryptedVisitedTiles>,
+}
+
+#[callback_accounts("reveal_standings")]
+#[derive(Accounts)]
+pub struct RevealStandingsCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_STANDINGS))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    /// CHECK: bound to the output signature by verify_output
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player_account.wallet.as_ref()],
+        bump = player_account.bump,
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"result", game.key().as_ref()],
+        bump = game_result.bump,
+    )]
+    pub game_result: Account<'info, GameResult>,
 }
 
 // Comp def init contexts (one per encrypted instruction)
+#[init_computation_definition_accounts("init_map", payer)]
 #[derive(Accounts)]
 pub struct InitMapCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    // ... Arcium comp_def accounts
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by the arcium program; not yet initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
 }
 
+#[init_computation_definition_accounts("init_buried_layer", payer)]
 #[derive(Accounts)]
 pub struct InitBuriedLayerCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by the arcium program; not yet initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
 }
 
+#[init_computation_definition_accounts("register_player", payer)]
 #[derive(Accounts)]
 pub struct InitRegisterPlayerCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by the arcium program; not yet initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
 }
 
+#[init_computation_definition_accounts("move_player", payer)]
 #[derive(Accounts)]
 pub struct InitMoveCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by the arcium program; not yet initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
 }
 
+#[init_computation_definition_accounts("explore", payer)]
 #[derive(Accounts)]
 pub struct InitExploreCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by the arcium program; not yet initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
 }
 
+#[init_computation_definition_accounts("bury", payer)]
 #[derive(Accounts)]
 pub struct InitBuryCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by the arcium program; not yet initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
 }
 
+#[init_computation_definition_accounts("dig", payer)]
 #[derive(Accounts)]
 pub struct InitDigCompDef<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by the arcium program; not yet initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[init_computation_definition_accounts("descend", payer)]
+#[derive(Accounts)]
+pub struct InitDescendCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by the arcium program; not yet initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
 }
 
+#[init_computation_definition_accounts("use_item", payer)]
 #[derive(Accounts)]
+pub struct InitUseItemCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by the arcium program; not yet initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[init_computation_definition_accounts("reveal_standings", payer)]
+#[derive(Accounts)]
+pub struct InitRevealStandingsCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by the arcium program; not yet initialized
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("init_buried_layer", authority)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, level: u8)]
 pub struct InitBuried<'info> {
+    #[account(mut, has_one = authority @ GameError::Unauthorized)]
+    pub game: Account<'info, Game>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<EncryptedBuriedLayer>(),
+        seeds = [b"buried", game.key().as_ref(), &[level]],
+        bump,
+    )]
+    pub encrypted_buried_layer: AccountLoader<'info, EncryptedBuriedLayer>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = authority,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_BURIED_LAYER))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[queue_computation_accounts("init_buried_layer", authority)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64, level: u8)]
+pub struct RetryBuriedInit<'info> {
+    #[account(mut, has_one = authority @ GameError::Unauthorized)]
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"buried", game.key().as_ref(), &[level]],
+        bump = encrypted_buried_layer.load()?.bump,
+        constraint = encrypted_buried_layer.load()?.initialized == 0 @ GameError::LevelAlreadyReady,
+    )]
+    pub encrypted_buried_layer: AccountLoader<'info, EncryptedBuriedLayer>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = authority,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut, address = derive_mempool_pda!())]
+    /// CHECK: mempool_account, checked by the arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!())]
+    /// CHECK: executing_pool, checked by the arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(mut, address = derive_comp_pda!(computation_offset))]
+    /// CHECK: computation_account, checked by the arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_BURIED_LAYER))]
+    pub comp_def_account: Box<Account<'info, ComputationDefinitionAccount>>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account))]
+    pub cluster_account: Box<Account<'info, Cluster>>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Box<Account<'info, FeePool>>,
+    #[account(address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Box<Account<'info, ClockAccount>>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[derive(Accounts)]
+pub struct InitVault<'info> {
+    #[account(mut, has_one = authority @ GameError::Unauthorized)]
     pub game: Account<'info, Game>,
+    pub mint: Account<'info, Mint>,
+    // Owned by the game PDA, which signs payouts
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vault", game.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = game,
+    )]
+    pub vault: Account<'info, TokenAccount>,
     #[account(mut)]
     pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimWinnings<'info> {
+    // mut: the lamport pool is held by the game account
+    #[account(mut)]
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"result", game.key().as_ref()],
+        bump = game_result.bump,
+    )]
+    pub game_result: Account<'info, GameResult>,
+    #[account(
+        mut,
+        seeds = [b"player", game.key().as_ref(), player.key().as_ref()],
+        bump = player_account.bump,
+    )]
+    pub player_account: Account<'info, PlayerAccount>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    // Staked games only
+    #[account(
+        mut,
+        seeds = [b"vault", game.key().as_ref()],
+        bump,
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = game.mint,
+        token::authority = player,
+    )]
+    pub player_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawRake<'info> {
+    #[account(
+        mut,
+        has_one = authority @ GameError::Unauthorized,
+        constraint = !game.is_active @ GameError::GameStillRunning,
+    )]
+    pub game: Account<'info, Game>,
+    #[account(
+        mut,
+        seeds = [b"result", game.key().as_ref()],
+        bump = game_result.bump,
+    )]
+    pub game_result: Account<'info, GameResult>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    // Staked games only
+    #[account(
+        mut,
+        seeds = [b"vault", game.key().as_ref()],
+        bump,
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = game.mint,
+        token::authority = authority,
+    )]
+    pub authority_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
 }
 
 // =========================================================
@@ -496,6 +3908,7 @@ pub struct InitBuried<'info> {
 pub struct GameCreated {
     pub game: Pubkey,
     pub authority: Pubkey,
+    pub game_id: u64,
     pub timestamp: i64,
 }
 
@@ -506,14 +3919,96 @@ pub struct PlayerRegistered {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct PlayerEliminated {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub timestamp: i64,
+}
+
+/// A player took the stairs. Levels are public — the program needs them
+/// to route actions — but the staircase they used is not.
+#[event]
+pub struct PlayerDescended {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub level: u8,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct ActionPerformed {
     pub game: Pubkey,
     pub player: Pubkey,
     pub action_type: ActionType,
+    pub sequence: u64, // per game, starting at 1
     pub timestamp: i64,
 }
 
+#[event]
+pub struct ComputationQueued {
+    pub game: Pubkey,
+    pub computation_offset: u64,
+    pub sequence: u64,
+}
+
+/// Pairs with `ComputationQueued`; a queued sequence number with no
+/// finalization means its callback never arrived
+#[event]
+pub struct ComputationFinalized {
+    pub game: Pubkey,
+    pub computation_offset: u64,
+    pub sequence: u64,
+}
+
+/// The other way a `ComputationQueued` ends. Why the circuit rejected an
+/// action (bad target, not enough gold, ...) is never public: that comes
+/// back as a status code sealed into the player's result.
+#[event]
+pub struct ComputationFailed {
+    pub game: Pubkey,
+    pub computation_offset: u64,
+    pub sequence: u64,
+    pub reason: FailureReason,
+}
+
+#[event]
+pub struct TurnAdvanced {
+    pub game: Pubkey,
+    pub seat: u32, // whose turn it now is
+    pub deadline: i64,
+    pub skipped: bool, // previous player timed out or was eliminated
+}
+
+#[event]
+pub struct GameEnded {
+    pub game: Pubkey,
+    pub reason: EndReason,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GameResolved {
+    pub game: Pubkey,
+    pub winner: Pubkey,
+    pub winning_gold: u16,
+}
+
+#[event]
+pub struct WinningsClaimed {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub lamports: u64,
+    pub tokens: u64,
+}
+
+#[event]
+pub struct RakeWithdrawn {
+    pub game: Pubkey,
+    pub lamports: u64,
+    pub tokens: u64,
+}
+
 #[event]
 pub struct ExploreResultEvent {
     pub game: Pubkey,
@@ -535,6 +4030,20 @@ pub struct MoveResultEvent {
     pub encrypted_result: Vec<u8>,
 }
 
+#[event]
+pub struct DescendResultEvent {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub encrypted_result: Vec<u8>,
+}
+
+#[event]
+pub struct UseItemResultEvent {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub encrypted_result: Vec<u8>,
+}
+
 #[event]
 pub struct BuryResultEvent {
     pub game: Pubkey,
@@ -552,6 +4061,32 @@ pub enum ActionType {
     Explore,
     Bury,
     Dig,
+    Register,
+    Descend,
+    UseItem,
+}
+
+/// How the prize pools are split once standings are revealed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PayoutCurve {
+    Proportional,   // by final gold
+    WinnerTakesAll, // highest gold, earliest revealed on ties
+    TopThree,       // 50/30/20 by final gold rank
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FailureReason {
+    // Arcium's failure report carries no detail, so every failure it
+    // delivers maps to Aborted
+    Aborted,  // the cluster reported the computation failed
+    TimedOut, // no callback; released by abort_pending
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum EndReason {
+    Authority,
+    DeadlineReached,
+    AllTreasuresClaimed,
 }
 
 #[error_code]
@@ -562,4 +4097,62 @@ pub enum GameError {
     PlayerAlreadyRegistered,
     #[msg("Unauthorized")]
     Unauthorized,
+    #[msg("Computation output failed cluster signature verification")]
+    InvalidComputationOutput,
+    #[msg("Game is still running")]
+    GameStillRunning,
+    #[msg("Standing already revealed for this player")]
+    StandingAlreadyRevealed,
+    #[msg("Map size is not a supported tier")]
+    UnsupportedMapSize,
+    #[msg("Invalid game config")]
+    InvalidGameConfig,
+    #[msg("Player has been eliminated")]
+    PlayerEliminated,
+    #[msg("Stakes must be set before anyone registers, with a nonzero entry fee")]
+    InvalidStake,
+    #[msg("Staked games need the player's token account, the vault and the token program")]
+    StakeAccountsMissing,
+    #[msg("Not every standing has been revealed")]
+    StandingsPending,
+    #[msg("Winnings already claimed")]
+    WinningsAlreadyClaimed,
+    #[msg("Vault holds less than the unclaimed prize pool")]
+    VaultMismatch,
+    #[msg("Rake exceeds the maximum")]
+    InvalidEconomics,
+    #[msg("Game is full")]
+    GameFull,
+    #[msg("Balance does not cover the entry fee")]
+    Underpayment,
+    #[msg("Rake already withdrawn")]
+    RakeAlreadyWithdrawn,
+    #[msg("Not this player's turn")]
+    NotYourTurn,
+    #[msg("Current turn has not timed out")]
+    TurnNotExpired,
+    #[msg("Game is not in turn mode")]
+    TurnModeOff,
+    #[msg("Game or turn duration exceeds the maximum")]
+    InvalidDuration,
+    #[msg("Out of action points — wait for them to regenerate")]
+    Cooldown,
+    #[msg("Previous action is still being computed")]
+    ComputationPending,
+    #[msg("No action is pending for this computation")]
+    NoPendingComputation,
+    #[msg("Pending action has not timed out yet")]
+    PendingNotExpired,
+    #[msg("Level is outside the game's dungeon")]
+    InvalidLevel,
+    #[msg("Already on the deepest level")]
+    NoLevelBelow,
+    #[msg("The level is not set up yet")]
+    LevelNotReady,
+    #[msg("Player state is not set up yet — retry the registration if it failed")]
+    PlayerNotReady,
+    #[msg("The level is already set up")]
+    LevelAlreadyReady,
+    #[msg("Arithmetic overflow")]
+    Overflow,
 }
@@ -9,27 +9,88 @@ use arcis_imports::*;
 mod circuits {
     use arcis_imports::*;
 
+    /// Tile capacity of every map. Arcis needs static array sizes, so all
+    /// size tiers (8x8, 10x10, 12x12) share one 12x12 buffer and the game's
+    /// MapConfig decides how much of it is in play. 12x12 is the largest
+    /// grid whose ciphertexts still fit in a single program-created account.
+    const MAX_TILES: usize = 144;
+
+    const EMPTY_TILE: MapTile = MapTile { tile_type: 0, value: 0 };
+
+    /// Health every player starts with; healing springs never raise it
+    /// past this cap
+    pub const MAX_HEALTH: u16 = 100;
+
+    /// Portal pairs init_map can link. Linking scans the map once per
+    /// pair, so this bounds its cost however many a game asks for.
+    pub const MAX_PORTAL_PAIRS: usize = 4;
+
+    /// How much a healing spring restores, drawn per spring
+    const SPRING_HEAL_MIN: u16 = 10;
+    const SPRING_HEAL_MAX: u16 = 40;
+
+    /// Item kinds, each indexing its count in `PlayerState.inventory`
+    pub const ITEM_SHOVEL: u8 = 0;
+    pub const ITEM_TRAP_DETECTOR: u8 = 1;
+    pub const ITEM_MAP_FRAGMENT: u8 = 2;
+    pub const ITEM_POTION: u8 = 3;
+    pub const ITEM_KINDS: usize = 4;
+
+    /// `DigResult.item` when the dig picked nothing up
+    pub const NO_ITEM: u8 = 4;
+
+    /// Health a potion restores, up to MAX_HEALTH
+    const POTION_HEAL: u16 = 50;
+
+    /// Status codes sealed into every action result. A rejected action
+    /// changes nothing but still completes, so only the requester learns
+    /// that (and why) it failed — its public outputs and re-encrypted
+    /// state look the same as for any other action.
+    pub const STATUS_OK: u8 = 0;
+    pub const STATUS_NOT_ADJACENT: u8 = 1;
+    pub const STATUS_OUT_OF_BOUNDS: u8 = 2;
+    pub const STATUS_NOT_ENOUGH_GOLD: u8 = 3;
+    pub const STATUS_INVALID_AMOUNT: u8 = 4;
+    pub const STATUS_PILE_FULL: u8 = 5;
+    pub const STATUS_DEAD: u8 = 6;
+    pub const STATUS_NO_STAIRS: u8 = 7;
+    pub const STATUS_BLOCKED: u8 = 8;
+    pub const STATUS_NO_ITEM: u8 = 9;
+    pub const STATUS_NO_WALL: u8 = 10;
+
     // =========================================================
     // DATA STRUCTURES (all encrypted when stored)
     // =========================================================
 
     /// Single tile in the base map
+    ///
+    /// `value` by type: treasure amount, trap damage, health a spring
+    /// restores, the row-major index of a portal's twin, or the item kind
+    /// a cache holds; 0 otherwise.
     #[derive(Clone, Copy)]
     pub struct MapTile {
-        pub tile_type: u8,  // 0 = empty, 1 = treasure, 2 = trap
-        pub value: u16,     // treasure amount or trap damage
+        // 0 = empty, 1 = treasure, 2 = trap, 3 = staircase, 4 = wall,
+        // 5 = spring, 6 = portal, 7 = item cache
+        pub tile_type: u8,
+        pub value: u16,
     }
 
-    /// The full 10x10 encrypted game map (100 tiles)
+    /// One level of the encrypted game map, row-major with the game's width
     /// Stored as Enc<Mxe, MapMatrix> — only MPC nodes can read during computation
     pub struct MapMatrix {
-        pub tiles: [MapTile; 100],
+        pub tiles: [MapTile; MAX_TILES],
     }
 
-    /// Buried loot layer — independent from base map
+    /// Buried loot layer for one level — independent from base map
     /// No player wallet addresses are stored in this structure
     pub struct BuriedLayer {
-        pub loot: [u16; 100], // Amount buried at each tile index
+        pub loot: [u16; MAX_TILES], // Amount buried at each tile index
+    }
+
+    /// Tiles this player has already explored on their current level —
+    /// each tile's effect resolves at most once per player
+    pub struct VisitedTiles {
+        pub visited: [bool; MAX_TILES],
     }
 
     /// Per-player encrypted state
@@ -38,16 +99,32 @@ mod circuits {
         pub y: u8,
         pub gold: u16,
         pub health: u16,
+        pub level: u8, // dungeon level, 0 = surface
+        pub inventory: [u8; ITEM_KINDS], // count held of each item kind
+    }
+
+    /// Public per-game map parameters, passed in plaintext from `Game.config`
+    /// The program validates the tier and that all counts fit before queueing.
+    pub struct MapConfig {
+        pub width: u8,
+        pub height: u8,
+        pub treasure_count: u8,
+        pub trap_count: u8,
+        pub treasure_min: u16,
+        pub treasure_max: u16,
+        pub trap_min: u16,
+        pub trap_max: u16,
+        pub levels: u8, // maps stacked in the dungeon, at least 1
+        pub wall_count: u8,
+        pub spring_count: u8,
+        pub portal_pairs: u8, // at most MAX_PORTAL_PAIRS
+        pub item_count: u8,
     }
 
     // =========================================================
     // INPUT/OUTPUT STRUCTURES
     // =========================================================
 
-    pub struct InitMapInput {
-        pub seed: u64,
-    }
-
     pub struct MoveInput {
         pub target_x: u8,
         pub target_y: u8,
@@ -69,24 +146,53 @@ mod circuits {
         pub target_y: u8,
     }
 
+    /// The target is only used by the shovel
+    pub struct UseItemInput {
+        pub item: u8,
+        pub target_x: u8,
+        pub target_y: u8,
+    }
+
+    pub struct DescendResult {
+        pub status: u8,
+        pub level: u8,
+    }
+
+    /// The explored tile as stored (see `MapTile` for what `value`
+    /// means per type) plus the player's health once it has resolved
     pub struct ExploreResult {
+        pub status: u8,
         pub tile_type: u8,
         pub value: u16,
+        pub health: u16,
     }
 
     pub struct DigResult {
-        pub found_type: u8,   // 0 = nothing, 1 = treasure/loot, 2 = trap
-        pub total_value: u16, // Combined value from both layers
-        pub health_lost: u16, // Trap damage if any
+        pub status: u8,
+        pub found_type: u8,       // 0 = nothing, 1 = treasure/loot, otherwise the base tile type
+        pub total_value: u16,     // Combined value from both layers
+        pub health_lost: u16,     // Trap damage if any
+        pub health_restored: u16, // Spring healing if any
+        pub item: u8,             // Item kind picked up, NO_ITEM if none
+    }
+
+    /// `value` by item: 1 if a trap is adjacent (detector), the
+    /// staircase's tile index or MAX_TILES if the level has none (map
+    /// fragment), the new health (potion); 0 for the shovel and on failure
+    pub struct UseItemResult {
+        pub status: u8,
+        pub item: u8,
+        pub value: u16,
     }
 
     pub struct MoveResult {
+        pub status: u8,
         pub new_x: u8,
         pub new_y: u8,
     }
 
     pub struct BuryResult {
-        pub success: u8,
+        pub status: u8,
         pub new_gold: u16,
     }
 
@@ -94,17 +200,6 @@ mod circuits {
     // HELPER FUNCTIONS
     // =========================================================
 
-    /// Simple PRNG for map generation inside MPC
-    /// Uses xorshift64 — deterministic from seed
-    fn prng_next(state: &mut u64) -> u64 {
-        let mut s = *state;
-        s ^= s << 13;
-        s ^= s >> 7;
-        s ^= s << 17;
-        *state = s;
-        s
-    }
-
     /// Absolute difference (constant-time for MPC safety)
     fn abs_diff(a: u8, b: u8) -> u8 {
         if a > b { a - b } else { b - a }
@@ -117,58 +212,249 @@ mod circuits {
         dx <= 1 && dy <= 1 && !(dx == 0 && dy == 0)
     }
 
+    /// u16 addition that clamps at u16::MAX instead of wrapping
+    /// Widened compare-and-select so the cost does not depend on the operands
+    fn saturating_add(a: u16, b: u16) -> u16 {
+        let sum = (a as u32) + (b as u32);
+        if sum > (u16::MAX as u32) {
+            u16::MAX
+        } else {
+            sum as u16
+        }
+    }
+
+    /// Deeper levels pay and hurt more: a base value scaled by depth,
+    /// clamped at u16::MAX
+    fn level_scaled(value: u16, level: u8) -> u16 {
+        let scaled = (value as u32) * (level as u32 + 1);
+        if scaled > (u16::MAX as u32) {
+            u16::MAX
+        } else {
+            scaled as u16
+        }
+    }
+
+    /// Heal by `amount`, never past MAX_HEALTH
+    fn heal(health: u16, amount: u16) -> u16 {
+        let healed = (health as u32) + (amount as u32);
+        if healed > (MAX_HEALTH as u32) {
+            MAX_HEALTH
+        } else {
+            healed as u16
+        }
+    }
+
+    /// Walls, staircases and portals are part of the level: digging
+    /// leaves them standing
+    fn is_fixture(tile: &MapTile) -> bool {
+        tile.tile_type == 3 || tile.tile_type == 4 || tile.tile_type == 6
+    }
+
+    /// Dead players (health 0) may not act
+    fn is_alive(p: &PlayerState) -> bool {
+        p.health > 0
+    }
+
+    /// Check that a position lies on the game's map
+    fn in_bounds(cfg: &MapConfig, x: u8, y: u8) -> bool {
+        x < cfg.width && y < cfg.height
+    }
+
     /// Convert (x, y) to flat index
-    fn tile_index(x: u8, y: u8) -> usize {
-        (y as usize) * 10 + (x as usize)
+    fn tile_index(cfg: &MapConfig, x: u8, y: u8) -> usize {
+        (y as usize) * (cfg.width as usize) + (x as usize)
+    }
+
+    /// Whether a living player may act on the tile at (x, y)
+    fn target_status(cfg: &MapConfig, p: &PlayerState, x: u8, y: u8) -> u8 {
+        if !is_alive(p) {
+            STATUS_DEAD
+        } else if !is_adjacent(p.x, p.y, x, y) {
+            STATUS_NOT_ADJACENT
+        } else if !in_bounds(cfg, x, y) {
+            STATUS_OUT_OF_BOUNDS
+        } else {
+            STATUS_OK
+        }
+    }
+
+    // ---------------------------------------------------------
+    // Oblivious tile access
+    //
+    // Every tile index derived from a player's input or a random draw is
+    // secret, so no circuit indexes a tile array with it directly. These
+    // helpers scan all MAX_TILES slots and select/update the target with
+    // a compare-and-mux per slot: the same gates run whatever the index,
+    // the map size or `enabled`, so the access pattern reveals nothing.
+    //
+    // Cost: MAX_TILES (144) index comparisons and element-wide muxes per
+    // call, even on an 8x8 map. explore and dig each make two reads and
+    // two writes, bury and use_item one of each, move_player and descend
+    // one read. An out-of-range index matches no slot.
+    // ---------------------------------------------------------
+
+    /// `items[idx]`, or `default` if not `enabled`
+    fn oblivious_read<T: Copy>(items: &[T; MAX_TILES], idx: usize, enabled: bool, default: T) -> T {
+        let mut found = default;
+        for j in 0..MAX_TILES {
+            if enabled && j == idx {
+                found = items[j];
+            }
+        }
+        found
+    }
+
+    /// `items[idx] = value` if `enabled`; every slot is rewritten either way
+    fn oblivious_write<T: Copy>(items: &mut [T; MAX_TILES], idx: usize, value: T, enabled: bool) {
+        for j in 0..MAX_TILES {
+            if enabled && j == idx {
+                items[j] = value;
+            }
+        }
+    }
+
+    /// 64 bits of the nodes' joint randomness. Each draw is fresh, so
+    /// tile values seen in play say nothing about the rest of the map.
+    fn random_u64() -> u64 {
+        ArcisRNG::gen_integer_from_width(64) as u64
+    }
+
+    /// Draw a value uniformly from [min, max]
+    fn random_range(min: u16, max: u16) -> u16 {
+        min + (random_u64() % ((max - min) as u64 + 1)) as u16
     }
 
     // =========================================================
     // MPC INSTRUCTIONS
     // =========================================================
 
-    /// Initialize the encrypted game map with seeded PRNG
-    /// Called once per game. Map encrypted to MXE key.
-    /// 
-    /// Privacy: The seed determines map layout. After this call,
-    /// the map exists ONLY in encrypted form inside the MXE.
+    /// Initialize one level of the encrypted game map from randomness
+    /// the cluster draws itself. Called once per level. Map encrypted to
+    /// MXE key.
+    ///
+    /// Treasure and trap values scale with depth: level `n` draws from
+    /// (n + 1) times the configured ranges. Every level but the deepest
+    /// hides one staircase down.
+    ///
+    /// Lays out the exact tile multiset — spawn, then the treasures, traps,
+    /// staircase, walls, springs, portals and item caches, then empty
+    /// tiles — and Fisher-Yates shuffles every tile but the spawn. Both
+    /// passes run a fixed MAX_TILES iterations with a fixed number of
+    /// random draws, so the cost never depends on the draws and the
+    /// configured counts are always placed exactly. Each shuffle step swaps
+    /// with a secret slot through one oblivious read and one oblivious
+    /// write.
+    ///
+    /// Portals are laid out tagged with their pair number. Once shuffled,
+    /// a final pass finds where each pair landed and points every portal's
+    /// value at its twin; it scans the map MAX_PORTAL_PAIRS times whatever
+    /// the configured count.
+    ///
+    /// Privacy: Every draw that shapes the layout comes from the nodes'
+    /// joint randomness, so nobody supplies it — not even the game
+    /// authority. It never leaves the computation, so the map exists
+    /// ONLY in encrypted form inside the MXE from the start.
     /// No client, validator, or indexer can read tile contents.
     #[instruction]
-    pub fn init_map(
-        seed_ctxt: Enc<Shared, InitMapInput>,
-    ) -> Enc<Mxe, MapMatrix> {
-        let seed_input = seed_ctxt.to_arcis();
-        let mut rng_state = seed_input.seed;
+    pub fn init_map(cfg: MapConfig, level: u8) -> Enc<Mxe, MapMatrix> {
+        let tiles = (cfg.width as usize) * (cfg.height as usize);
+        let treasure_end = 1 + cfg.treasure_count as usize;
+        let trap_end = treasure_end + cfg.trap_count as usize;
+        let stairs_end = if level + 1 < cfg.levels { trap_end + 1 } else { trap_end };
+        let wall_end = stairs_end + cfg.wall_count as usize;
+        let spring_end = wall_end + cfg.spring_count as usize;
+        let portal_end = spring_end + 2 * cfg.portal_pairs as usize;
+        let cache_end = portal_end + cfg.item_count as usize;
+        let treasure_min = level_scaled(cfg.treasure_min, level);
+        let treasure_max = level_scaled(cfg.treasure_max, level);
+        let trap_min = level_scaled(cfg.trap_min, level);
+        let trap_max = level_scaled(cfg.trap_max, level);
 
         let mut map = MapMatrix {
-            tiles: [MapTile { tile_type: 0, value: 0 }; 100],
+            tiles: [EMPTY_TILE; MAX_TILES],
         };
 
-        // Place 15 treasures
-        let mut placed: u8 = 0;
-        while placed < 15 {
-            let idx = (prng_next(&mut rng_state) % 100) as usize;
-            // Skip tile (0,0) — player spawn
-            if idx != 0 && map.tiles[idx].tile_type == 0 {
-                let value = 5 + (prng_next(&mut rng_state) % 46) as u16; // 5-50
-                map.tiles[idx] = MapTile {
+        // Tile multiset in order; slot 0 is the spawn and stays empty
+        for k in 1..MAX_TILES {
+            let treasure_value = random_range(treasure_min, treasure_max);
+            let trap_value = random_range(trap_min, trap_max);
+            let spring_value = random_range(SPRING_HEAL_MIN, SPRING_HEAL_MAX);
+            let item_kind = random_range(0, ITEM_KINDS as u16 - 1);
+            map.tiles[k] = if k < treasure_end {
+                MapTile {
                     tile_type: 1, // treasure
-                    value,
-                };
-                placed += 1;
-            }
+                    value: treasure_value,
+                }
+            } else if k < trap_end {
+                MapTile {
+                    tile_type: 2, // trap
+                    value: trap_value,
+                }
+            } else if k < stairs_end {
+                MapTile {
+                    tile_type: 3, // staircase
+                    value: 0,
+                }
+            } else if k < wall_end {
+                MapTile {
+                    tile_type: 4, // wall
+                    value: 0,
+                }
+            } else if k < spring_end {
+                MapTile {
+                    tile_type: 5, // healing spring
+                    value: spring_value,
+                }
+            } else if k < portal_end {
+                MapTile {
+                    tile_type: 6, // portal, tagged with its pair for now
+                    value: ((k - spring_end) / 2) as u16,
+                }
+            } else if k < cache_end {
+                MapTile {
+                    tile_type: 7, // item cache
+                    value: item_kind,
+                }
+            } else {
+                EMPTY_TILE
+            };
         }
 
-        // Place 10 traps
-        placed = 0;
-        while placed < 10 {
-            let idx = (prng_next(&mut rng_state) % 100) as usize;
-            if idx != 0 && map.tiles[idx].tile_type == 0 {
-                let value = 5 + (prng_next(&mut rng_state) % 26) as u16; // 5-30
-                map.tiles[idx] = MapTile {
-                    tile_type: 2, // trap
-                    value,
-                };
-                placed += 1;
+        // Shuffle slots 1..tiles; slots past the map's size sit out
+        for k in 0..MAX_TILES - 2 {
+            let i = MAX_TILES - 1 - k;
+            let active = i < tiles;
+            let j = 1 + (random_u64() % (i as u64)) as usize; // uniform in [1, i]
+            let here = map.tiles[i];
+            let there = oblivious_read(&map.tiles, j, active, here);
+            oblivious_write(&mut map.tiles, j, here, active);
+            map.tiles[i] = there;
+        }
+
+        // Find both ends of every pair before rewriting any tag: a twin's
+        // index could equal a later pair's tag. Missing pairs keep
+        // MAX_TILES, which matches no slot.
+        let mut first = [MAX_TILES; MAX_PORTAL_PAIRS];
+        let mut second = [MAX_TILES; MAX_PORTAL_PAIRS];
+        for pair in 0..MAX_PORTAL_PAIRS {
+            for j in 0..MAX_TILES {
+                let tile = map.tiles[j];
+                if tile.tile_type == 6 && tile.value as usize == pair {
+                    if first[pair] == MAX_TILES {
+                        first[pair] = j;
+                    } else {
+                        second[pair] = j;
+                    }
+                }
+            }
+        }
+        for pair in 0..MAX_PORTAL_PAIRS {
+            for j in 0..MAX_TILES {
+                if j == first[pair] {
+                    map.tiles[j].value = second[pair] as u16;
+                } else if j == second[pair] {
+                    map.tiles[j].value = first[pair] as u16;
+                }
             }
         }
 
@@ -181,46 +467,81 @@ mod circuits {
     #[instruction]
     pub fn init_buried_layer() -> Enc<Mxe, BuriedLayer> {
         let layer = BuriedLayer {
-            loot: [0u16; 100],
+            loot: [0u16; MAX_TILES],
         };
         Mxe.from_arcis(layer)
     }
 
     /// Register a new player with starting position and resources
     #[instruction]
-    pub fn register_player() -> Enc<Mxe, PlayerState> {
+    pub fn register_player() -> (Enc<Mxe, PlayerState>, Enc<Mxe, VisitedTiles>) {
         let player = PlayerState {
             x: 0,
             y: 0,
             gold: 20,
-            health: 100,
+            health: MAX_HEALTH,
+            level: 0,
+            inventory: [0u8; ITEM_KINDS],
+        };
+        let visited = VisitedTiles {
+            visited: [false; MAX_TILES],
         };
-        Mxe.from_arcis(player)
+        (Mxe.from_arcis(player), Mxe.from_arcis(visited))
     }
 
     /// Move player to an adjacent tile
     /// MPC verifies adjacency without revealing positions to anyone else
     ///
+    /// Walls refuse the move with STATUS_BLOCKED. Stepping onto a portal
+    /// lands the player on its twin, wherever that is on the level.
+    ///
+    /// Traps and springs do nothing here: like treasure, a tile's contents
+    /// act when the player explores or digs it, and those circuits track
+    /// the visited tiles that make each one act once per player. Resolving
+    /// them on a move would also make the move reveal whether a trap went
+    /// off or the player died, since the program has to record both.
+    ///
     /// Privacy: Player position is encrypted. The Solana tx only shows
-    /// "player called move" — not the coordinates.
+    /// "player called move" — not the coordinates, and not whether a wall
+    /// or portal was involved. The map is read obliviously and not written.
     #[instruction]
     pub fn move_player(
         input: Enc<Shared, MoveInput>,
         player_state: Enc<Mxe, PlayerState>,
+        map: Enc<Mxe, MapMatrix>,
+        cfg: MapConfig,
         requester: Shared,
     ) -> (Enc<Shared, MoveResult>, Enc<Mxe, PlayerState>) {
         let i = input.to_arcis();
         let mut p = player_state.to_arcis();
+        let m = map.to_arcis();
 
         // Verify adjacency inside MPC — no position data leaves encrypted space
-        let valid = is_adjacent(p.x, p.y, i.target_x, i.target_y);
-        assert!(valid); // Fails computation if not adjacent
-        assert!(i.target_x < 10 && i.target_y < 10); // Bounds check
+        let target = target_status(&cfg, &p, i.target_x, i.target_y);
+        let idx = tile_index(&cfg, i.target_x, i.target_y);
+        let tile = oblivious_read(&m.tiles, idx, target == STATUS_OK, EMPTY_TILE);
+        let status = if target != STATUS_OK {
+            target
+        } else if tile.tile_type == 4 {
+            STATUS_BLOCKED
+        } else {
+            STATUS_OK
+        };
 
-        p.x = i.target_x;
-        p.y = i.target_y;
+        if status == STATUS_OK {
+            if tile.tile_type == 6 {
+                // Portal — step out of its twin
+                let exit = tile.value as usize;
+                p.x = (exit % (cfg.width as usize)) as u8;
+                p.y = (exit / (cfg.width as usize)) as u8;
+            } else {
+                p.x = i.target_x;
+                p.y = i.target_y;
+            }
+        }
 
         let result = MoveResult {
+            status,
             new_x: p.x,
             new_y: p.y,
         };
@@ -235,45 +556,85 @@ mod circuits {
     /// Privacy: The full map stays encrypted. Only the single tile's
     /// content is revealed, and ONLY to the player who requested it.
     /// Other players, validators, and indexers learn nothing.
+    ///
+    /// A tile's effect applies only on this player's first visit. Treasure
+    /// is taken off the map when collected, so it can be claimed once per
+    /// game; a trap stays armed for other players but hurts each player
+    /// at most once, and a spring heals each player at most once. Walls,
+    /// staircases, portals and item caches only show what they are — a
+    /// portal's value tells the player where its twin is, a cache's what
+    /// digging it up would yield. Like dig, the only public outputs are
+    /// whether a treasure was claimed, whether a trap went off and whether
+    /// the player died.
     #[instruction]
     pub fn explore(
         input: Enc<Shared, ExploreInput>,
         player_state: Enc<Mxe, PlayerState>,
         map: Enc<Mxe, MapMatrix>,
+        visited_tiles: Enc<Mxe, VisitedTiles>,
+        cfg: MapConfig,
         requester: Shared,
-    ) -> (Enc<Shared, ExploreResult>, Enc<Mxe, PlayerState>) {
+    ) -> (
+        Enc<Shared, ExploreResult>,
+        Enc<Mxe, PlayerState>,
+        Enc<Mxe, MapMatrix>,
+        Enc<Mxe, VisitedTiles>,
+        bool,
+        bool,
+        bool,
+    ) {
         let i = input.to_arcis();
         let mut p = player_state.to_arcis();
-        let m = map.to_arcis();
+        let mut m = map.to_arcis();
+        let mut v = visited_tiles.to_arcis();
 
         // Verify player is adjacent to target tile
-        let valid = is_adjacent(p.x, p.y, i.target_x, i.target_y);
-        assert!(valid);
-        assert!(i.target_x < 10 && i.target_y < 10);
+        let status = target_status(&cfg, &p, i.target_x, i.target_y);
+        let ok = status == STATUS_OK;
 
-        let idx = tile_index(i.target_x, i.target_y);
-        let tile = m.tiles[idx];
+        let idx = tile_index(&cfg, i.target_x, i.target_y);
+        let tile = oblivious_read(&m.tiles, idx, ok, EMPTY_TILE);
+        let first_visit = ok && !oblivious_read(&v.visited, idx, ok, false);
+        oblivious_write(&mut v.visited, idx, true, ok);
 
         // Apply effect to encrypted player state
-        if tile.tile_type == 1 {
-            // Treasure — add to gold
-            p.gold += tile.value;
-        } else if tile.tile_type == 2 {
+        let treasure_claimed = first_visit && tile.tile_type == 1;
+        let trap_triggered = first_visit && tile.tile_type == 2;
+        oblivious_write(&mut m.tiles, idx, EMPTY_TILE, treasure_claimed);
+        if treasure_claimed {
+            // Treasure — add to gold (the tile was cleared above)
+            p.gold = saturating_add(p.gold, tile.value);
+        } else if trap_triggered {
             // Trap — deduct health
             if p.health > tile.value {
                 p.health -= tile.value;
             } else {
                 p.health = 0;
             }
+        } else if first_visit && tile.tile_type == 5 {
+            // Healing spring — restore health up to the cap
+            p.health = heal(p.health, tile.value);
         }
 
         let result = ExploreResult {
+            status,
             tile_type: tile.tile_type,
             value: tile.value,
+            health: p.health,
         };
 
+        let eliminated = !is_alive(&p);
+
         // Result sealed to requester's key — only they can decrypt
-        (requester.from_arcis(result), Mxe.from_arcis(p))
+        (
+            requester.from_arcis(result),
+            Mxe.from_arcis(p),
+            Mxe.from_arcis(m),
+            Mxe.from_arcis(v),
+            treasure_claimed.reveal(),
+            trap_triggered.reveal(),
+            eliminated.reveal(),
+        )
     }
 
     /// Bury loot at a tile with ZERO PUBLIC LINKAGE
@@ -291,32 +652,45 @@ mod circuits {
         input: Enc<Shared, BuryInput>,
         player_state: Enc<Mxe, PlayerState>,
         buried_layer: Enc<Mxe, BuriedLayer>,
+        cfg: MapConfig,
         requester: Shared,
     ) -> (Enc<Shared, BuryResult>, Enc<Mxe, PlayerState>, Enc<Mxe, BuriedLayer>) {
         let i = input.to_arcis();
         let mut p = player_state.to_arcis();
         let mut b = buried_layer.to_arcis();
 
-        // Verify player has enough gold (encrypted comparison)
-        assert!(p.gold >= i.amount);
-        assert!(i.amount > 0);
-        assert!(i.target_x < 10 && i.target_y < 10);
-
-        // Verify adjacency
-        let valid = is_adjacent(p.x, p.y, i.target_x, i.target_y);
-        assert!(valid);
+        // Verify adjacency, then that the player has enough gold
+        // (encrypted comparison) and the tile's pile can hold it, so gold
+        // is never destroyed by a wrapped sum
+        let target = target_status(&cfg, &p, i.target_x, i.target_y);
+        let idx = tile_index(&cfg, i.target_x, i.target_y);
+        let pile = oblivious_read(&b.loot, idx, target == STATUS_OK, 0);
+        let status = if target != STATUS_OK {
+            target
+        } else if i.amount == 0 {
+            STATUS_INVALID_AMOUNT
+        } else if p.gold < i.amount {
+            STATUS_NOT_ENOUGH_GOLD
+        } else if i.amount > u16::MAX - pile {
+            STATUS_PILE_FULL
+        } else {
+            STATUS_OK
+        };
 
         // Deduct from player, add to buried layer
-        p.gold -= i.amount;
-        let idx = tile_index(i.target_x, i.target_y);
-        b.loot[idx] += i.amount;
+        let ok = status == STATUS_OK;
+        if ok {
+            p.gold -= i.amount;
+        }
+        let new_pile = if ok { pile + i.amount } else { pile };
+        oblivious_write(&mut b.loot, idx, new_pile, ok);
 
         // CRITICAL: No wallet address is stored in BuriedLayer
         // The connection between this player and this tile exists
         // ONLY inside this MPC computation and is never persisted
 
         let result = BuryResult {
-            success: 1,
+            status,
             new_gold: p.gold,
         };
 
@@ -334,43 +708,66 @@ mod circuits {
     /// but does NOT reveal which layer contributed what to other observers.
     /// This dual-layer check is impossible without MPC — on a public
     /// chain, both layers would be readable separately.
+    ///
+    /// Whatever the base tile held is consumed: a dug-up treasure is gone,
+    /// a sprung trap is disarmed, a tapped spring runs dry and a cache's
+    /// item goes into the digger's inventory, so every such tile resolves
+    /// once. Staircases, walls and portals stay put, and so does a cache
+    /// whose item the digger already holds u8::MAX of.
+    ///
+    /// The public outputs are whether a base-map treasure was dug up,
+    /// which feeds the public treasures_found stat and lets the program
+    /// end the game once every treasure is claimed (buried loot is never
+    /// reflected in it), whether a trap went off, feeding the public
+    /// traps_triggered stat, and whether the player died.
     #[instruction]
     pub fn dig(
         input: Enc<Shared, DigInput>,
         player_state: Enc<Mxe, PlayerState>,
         map: Enc<Mxe, MapMatrix>,
         buried_layer: Enc<Mxe, BuriedLayer>,
+        cfg: MapConfig,
         requester: Shared,
-    ) -> (Enc<Shared, DigResult>, Enc<Mxe, PlayerState>, Enc<Mxe, BuriedLayer>) {
+    ) -> (
+        Enc<Shared, DigResult>,
+        Enc<Mxe, PlayerState>,
+        Enc<Mxe, MapMatrix>,
+        Enc<Mxe, BuriedLayer>,
+        bool,
+        bool,
+        bool,
+    ) {
         let i = input.to_arcis();
         let mut p = player_state.to_arcis();
-        let m = map.to_arcis();
+        let mut m = map.to_arcis();
         let mut b = buried_layer.to_arcis();
 
         // Verify adjacency
-        let valid = is_adjacent(p.x, p.y, i.target_x, i.target_y);
-        assert!(valid);
-        assert!(i.target_x < 10 && i.target_y < 10);
+        let status = target_status(&cfg, &p, i.target_x, i.target_y);
+        let ok = status == STATUS_OK;
 
-        let idx = tile_index(i.target_x, i.target_y);
-        let base_tile = m.tiles[idx];
-        let buried_amount = b.loot[idx];
+        // A rejected dig finds nothing and disturbs nothing
+        let idx = tile_index(&cfg, i.target_x, i.target_y);
+        let base_tile = oblivious_read(&m.tiles, idx, ok, EMPTY_TILE);
+        let buried_amount = oblivious_read(&b.loot, idx, ok, 0);
 
         let mut total_value: u16 = 0;
         let mut health_lost: u16 = 0;
+        let mut health_restored: u16 = 0;
+        let mut item = NO_ITEM;
         let mut found_type: u8 = 0; // nothing
 
         // Check buried layer
         if buried_amount > 0 {
-            total_value += buried_amount;
-            b.loot[idx] = 0; // Clear buried loot
+            total_value = saturating_add(total_value, buried_amount);
             found_type = 1; // found loot
         }
+        oblivious_write(&mut b.loot, idx, 0, ok); // Clear buried loot
 
         // Check base map layer
         if base_tile.tile_type == 1 {
             // Base treasure
-            total_value += base_tile.value;
+            total_value = saturating_add(total_value, base_tile.value);
             found_type = 1;
         } else if base_tile.tile_type == 2 {
             // Base trap
@@ -380,24 +777,239 @@ mod circuits {
             } else {
                 p.health = 0;
             }
-            if found_type == 0 {
-                found_type = 2; // trap
+        } else if base_tile.tile_type == 5 {
+            // Healing spring
+            let healed = heal(p.health, base_tile.value);
+            health_restored = healed - p.health;
+            p.health = healed;
+        } else if base_tile.tile_type == 7 {
+            // Item cache — into the inventory, unless that stack is full
+            item = base_tile.value as u8;
+        }
+        let mut stack_full = false;
+        for k in 0..ITEM_KINDS {
+            if item as usize == k {
+                if p.inventory[k] < u8::MAX {
+                    p.inventory[k] += 1;
+                } else {
+                    stack_full = true;
+                }
             }
         }
+        if stack_full {
+            item = NO_ITEM;
+        }
+        if found_type == 0 {
+            found_type = base_tile.tile_type; // trap, staircase, wall, spring, portal or cache
+        }
 
-        // Add found treasure to player gold
-        p.gold += total_value;
+        // Base tile is resolved — clear it, unless it is part of the level
+        // or a cache left for someone with room
+        let consumed = ok && !is_fixture(&base_tile) && !stack_full;
+        oblivious_write(&mut m.tiles, idx, EMPTY_TILE, consumed);
+
+        // Add found treasure to player gold (clamped at u16::MAX)
+        p.gold = saturating_add(p.gold, total_value);
 
         let result = DigResult {
+            status,
             found_type,
             total_value,
             health_lost,
+            health_restored,
+            item,
         };
 
+        let treasure_claimed = base_tile.tile_type == 1;
+        let trap_triggered = base_tile.tile_type == 2;
+        let eliminated = !is_alive(&p);
+
         (
             requester.from_arcis(result),
             Mxe.from_arcis(p),
+            Mxe.from_arcis(m),
             Mxe.from_arcis(b),
+            treasure_claimed.reveal(),
+            trap_triggered.reveal(),
+            eliminated.reveal(),
+        )
+    }
+
+    /// Take the staircase the player stands on down to the next level
+    ///
+    /// The player arrives at the new level's spawn (0, 0) with a fresh
+    /// visited bitmap. Anywhere but on a staircase the descent is refused
+    /// with STATUS_NO_STAIRS.
+    ///
+    /// Privacy: Whether the player went down is public — the program
+    /// routes all their later actions to that level's map and buried
+    /// layer — but the staircase, and so where they stood, stays secret.
+    #[instruction]
+    pub fn descend(
+        player_state: Enc<Mxe, PlayerState>,
+        map: Enc<Mxe, MapMatrix>,
+        visited_tiles: Enc<Mxe, VisitedTiles>,
+        cfg: MapConfig,
+        requester: Shared,
+    ) -> (
+        Enc<Shared, DescendResult>,
+        Enc<Mxe, PlayerState>,
+        Enc<Mxe, VisitedTiles>,
+        bool,
+    ) {
+        let mut p = player_state.to_arcis();
+        let m = map.to_arcis();
+        let mut v = visited_tiles.to_arcis();
+
+        let idx = tile_index(&cfg, p.x, p.y);
+        let here = oblivious_read(&m.tiles, idx, true, EMPTY_TILE);
+        let status = if !is_alive(&p) {
+            STATUS_DEAD
+        } else if here.tile_type != 3 {
+            STATUS_NO_STAIRS
+        } else {
+            STATUS_OK
+        };
+
+        let descended = status == STATUS_OK;
+        if descended {
+            p.level += 1;
+            p.x = 0;
+            p.y = 0;
+        }
+        for j in 0..MAX_TILES {
+            v.visited[j] = v.visited[j] && !descended;
+        }
+
+        let result = DescendResult {
+            status,
+            level: p.level,
+        };
+
+        (
+            requester.from_arcis(result),
+            Mxe.from_arcis(p),
+            Mxe.from_arcis(v),
+            descended.reveal(),
         )
     }
+
+    /// Use one item from the player's inventory
+    ///
+    /// - Shovel: breaks down the adjacent wall at the target
+    /// - Trap detector: tells the player whether any adjacent tile holds
+    ///   a trap, but not which one or how many
+    /// - Map fragment: shows where this level's staircase is
+    /// - Potion: restores POTION_HEAL health, up to MAX_HEALTH
+    ///
+    /// Using an item the player doesn't hold is refused with
+    /// STATUS_NO_ITEM, a shovel aimed at anything but a wall with
+    /// STATUS_NO_WALL; neither consumes it.
+    ///
+    /// Privacy: The item, its target and its effect are sealed. The map
+    /// is scanned and rewritten in full whatever the item, so the
+    /// transaction shows only that some item was used.
+    #[instruction]
+    pub fn use_item(
+        input: Enc<Shared, UseItemInput>,
+        player_state: Enc<Mxe, PlayerState>,
+        map: Enc<Mxe, MapMatrix>,
+        cfg: MapConfig,
+        requester: Shared,
+    ) -> (Enc<Shared, UseItemResult>, Enc<Mxe, PlayerState>, Enc<Mxe, MapMatrix>) {
+        let i = input.to_arcis();
+        let mut p = player_state.to_arcis();
+        let mut m = map.to_arcis();
+
+        // An unknown kind matches no slot and so is never held
+        let mut held: u8 = 0;
+        for k in 0..ITEM_KINDS {
+            if i.item as usize == k {
+                held = p.inventory[k];
+            }
+        }
+
+        let shovel = i.item == ITEM_SHOVEL;
+        let target = target_status(&cfg, &p, i.target_x, i.target_y);
+        let idx = tile_index(&cfg, i.target_x, i.target_y);
+        let aimed = oblivious_read(&m.tiles, idx, shovel && target == STATUS_OK, EMPTY_TILE);
+        let status = if !is_alive(&p) {
+            STATUS_DEAD
+        } else if held == 0 {
+            STATUS_NO_ITEM
+        } else if shovel && target != STATUS_OK {
+            target
+        } else if shovel && aimed.tile_type != 4 {
+            STATUS_NO_WALL
+        } else {
+            STATUS_OK
+        };
+        let ok = status == STATUS_OK;
+
+        // Shovel — the wall becomes open ground
+        oblivious_write(&mut m.tiles, idx, EMPTY_TILE, ok && shovel);
+
+        // Detector and fragment share one scan of the level
+        let tiles = (cfg.width as usize) * (cfg.height as usize);
+        let mut trap_nearby = false;
+        let mut stairs = MAX_TILES;
+        for j in 0..MAX_TILES {
+            let on_map = j < tiles;
+            let x = (j % (cfg.width as usize)) as u8;
+            let y = (j / (cfg.width as usize)) as u8;
+            let tile = m.tiles[j];
+            if on_map && tile.tile_type == 2 && is_adjacent(p.x, p.y, x, y) {
+                trap_nearby = true;
+            }
+            if on_map && tile.tile_type == 3 {
+                stairs = j;
+            }
+        }
+
+        // Potion
+        if ok && i.item == ITEM_POTION {
+            p.health = heal(p.health, POTION_HEAL);
+        }
+
+        for k in 0..ITEM_KINDS {
+            if ok && i.item as usize == k {
+                p.inventory[k] -= 1;
+            }
+        }
+
+        let value = if !ok {
+            0
+        } else if i.item == ITEM_TRAP_DETECTOR {
+            if trap_nearby { 1 } else { 0 }
+        } else if i.item == ITEM_MAP_FRAGMENT {
+            stairs as u16
+        } else if i.item == ITEM_POTION {
+            p.health
+        } else {
+            0
+        };
+
+        let result = UseItemResult {
+            status,
+            item: i.item,
+            value,
+        };
+
+        (
+            requester.from_arcis(result),
+            Mxe.from_arcis(p),
+            Mxe.from_arcis(m),
+        )
+    }
+
+    /// Reveal a player's final gold once the game has ended
+    ///
+    /// Privacy: Runs only after the program has closed the game, so the
+    /// revealed balance can no longer be used to track anyone mid-game.
+    /// Position and health stay encrypted forever.
+    #[instruction]
+    pub fn reveal_standings(player_state: Enc<Mxe, PlayerState>) -> u16 {
+        let p = player_state.to_arcis();
+        p.gold.reveal()
+    }
 }
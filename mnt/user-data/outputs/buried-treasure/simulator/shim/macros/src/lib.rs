@@ -0,0 +1,82 @@
+// ============================================================
+// ARCIS SHIM — ATTRIBUTE MACROS
+// Plaintext stand-ins for #[encrypted] and #[instruction] so the
+// circuits compile as ordinary Rust inside the simulator
+// ============================================================
+
+use proc_macro::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// `#[encrypted] mod circuits { ... }`
+///
+/// Makes the module public so the simulator can reach it, and derives
+/// `Clone`, `Debug`, `PartialEq` and `Eq` on every struct so plaintext
+/// state can be snapshotted, compared and printed.
+#[proc_macro_attribute]
+pub fn encrypted(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut tokens: Vec<TokenTree> = item.into_iter().collect();
+    let body = match tokens.pop() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group,
+        _ => panic!("#[encrypted] expects `mod name {{ ... }}`"),
+    };
+
+    let mut out: Vec<TokenTree> = vec![Ident::new("pub", Span::call_site()).into()];
+    out.extend(tokens);
+    out.push(Group::new(Delimiter::Brace, derive_plaintext_traits(body.stream())).into());
+    out.into_iter().collect()
+}
+
+/// `#[instruction]` — circuits run as plain function calls
+#[proc_macro_attribute]
+pub fn instruction(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
+/// Walk the module's top-level items and prepend a derive to each struct
+fn derive_plaintext_traits(body: TokenStream) -> TokenStream {
+    let mut out: Vec<TokenTree> = Vec::new();
+    // Index in `out` where the current item (including its attributes) starts
+    let mut item_start = 0;
+
+    for token in body {
+        let ends_item = match &token {
+            TokenTree::Punct(punct) => punct.as_char() == ';',
+            TokenTree::Group(group) => group.delimiter() == Delimiter::Brace,
+            _ => false,
+        };
+        let is_struct = matches!(&token, TokenTree::Ident(ident) if ident.to_string() == "struct");
+
+        if is_struct {
+            let derives_clone = out[item_start..].iter().any(|tt| {
+                matches!(tt, TokenTree::Group(group)
+                    if group.delimiter() == Delimiter::Bracket
+                        && group.stream().to_string().starts_with("derive")
+                        && group.stream().to_string().contains("Clone"))
+            });
+            let traits = if derives_clone {
+                "Debug, PartialEq, Eq"
+            } else {
+                "Clone, Debug, PartialEq, Eq"
+            };
+            let derive = derive_attribute(traits);
+            out.splice(item_start..item_start, derive);
+        }
+
+        out.push(token);
+        if ends_item {
+            item_start = out.len();
+        }
+    }
+
+    out.into_iter().collect()
+}
+
+/// `#[derive(<traits>)]` as tokens
+fn derive_attribute(traits: &str) -> Vec<TokenTree> {
+    let inner: TokenStream = format!("derive({traits})")
+        .parse()
+        .expect("derive attribute parses");
+    vec![
+        Punct::new('#', Spacing::Alone).into(),
+        Group::new(Delimiter::Bracket, inner).into(),
+    ]
+}
@@ -0,0 +1,94 @@
+// ============================================================
+// ARCIS SHIM — PLAINTEXT arcis_imports
+// Same names the circuits import from Arcis, with no encryption:
+// Enc<Owner, T> is a transparent wrapper and reveal() is identity
+// ============================================================
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+pub use arcis_shim_macros::{encrypted, instruction};
+
+/// Value "encrypted" to an owner — plaintext in the simulator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Enc<Owner, T> {
+    value: T,
+    owner: PhantomData<Owner>,
+}
+
+impl<Owner, T> Enc<Owner, T> {
+    /// Decrypt inside the circuit
+    pub fn to_arcis(self) -> T {
+        self.value
+    }
+}
+
+/// The MXE cluster key
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Mxe;
+
+impl Mxe {
+    /// Seal a value to the MXE
+    pub fn from_arcis<T>(&self, value: T) -> Enc<Mxe, T> {
+        Enc {
+            value,
+            owner: PhantomData,
+        }
+    }
+}
+
+/// A client's shared-secret key
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Shared;
+
+impl Shared {
+    /// Seal a value to the client
+    pub fn from_arcis<T>(&self, value: T) -> Enc<Shared, T> {
+        Enc {
+            value,
+            owner: PhantomData,
+        }
+    }
+}
+
+thread_local! {
+    static RNG_STATE: Cell<u64> = const { Cell::new(0) };
+}
+
+/// The cluster's joint randomness — a seeded splitmix64 stream in the
+/// simulator, so every run can be replayed
+pub struct ArcisRNG;
+
+impl ArcisRNG {
+    /// Restart this thread's stream from `seed`
+    pub fn reseed(seed: u64) {
+        RNG_STATE.with(|state| state.set(seed));
+    }
+
+    /// Uniform integer of `width` bits (at most 64 here)
+    pub fn gen_integer_from_width(width: usize) -> u128 {
+        let next = RNG_STATE.with(|state| {
+            let s = state.get().wrapping_add(0x9e37_79b9_7f4a_7c15);
+            state.set(s);
+            let mut z = s;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        });
+        (next as u128) & ((1u128 << width.min(64)) - 1)
+    }
+}
+
+/// `.reveal()` on secret primitives — already public in plaintext
+pub trait Reveal: Sized {
+    fn reveal(self) -> Self {
+        self
+    }
+}
+
+impl Reveal for bool {}
+impl Reveal for u8 {}
+impl Reveal for u16 {}
+impl Reveal for u32 {}
+impl Reveal for u64 {}
+impl Reveal for u128 {}
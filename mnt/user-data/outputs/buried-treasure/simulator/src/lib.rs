@@ -0,0 +1,356 @@
+// ============================================================
+// BURIED TREASURE — PLAINTEXT REFERENCE SIMULATOR
+// Runs the exact circuits from encrypted-ixs on plaintext state
+// ============================================================
+//
+// The circuit source is compiled as-is against a plaintext shim of
+// `arcis_imports` (see shim/), so there is no second copy of the game
+// logic to drift out of sync. Rejected actions come back with a status
+// code, as on-chain. A circuit that panics instead is reported as
+// `Aborted` and leaves state untouched, matching what the program sees
+// when an MPC computation fails.
+
+use std::panic::{self, AssertUnwindSafe};
+
+// Written for the Arcis compiler, not for clippy
+#[allow(clippy::all, unused_imports)]
+#[path = "../../encrypted-ixs/src/lib.rs"]
+mod ixs;
+
+pub use ixs::circuits::*;
+
+use arcis_imports::{ArcisRNG, Mxe, Shared};
+
+/// The circuit panicked — the computation aborted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Aborted;
+
+pub type PlayerId = usize;
+
+/// Plaintext counterpart of one player's encrypted PDAs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Player {
+    pub state: PlayerState,
+    pub visited: VisitedTiles,
+}
+
+/// What explore hands back: the player's sealed result plus the
+/// values the circuit reveals publicly
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExploreOutcome {
+    pub result: ExploreResult,
+    pub treasure_claimed: bool,
+    pub trap_triggered: bool,
+    pub eliminated: bool,
+}
+
+/// What dig hands back: the player's sealed result plus the
+/// values the circuit reveals publicly
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DigOutcome {
+    pub result: DigResult,
+    pub treasure_claimed: bool,
+    pub trap_triggered: bool,
+    pub eliminated: bool,
+}
+
+/// What descend hands back: the player's sealed result plus whether
+/// they went down, which the circuit reveals publicly
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DescendOutcome {
+    pub result: DescendResult,
+    pub descended: bool,
+}
+
+/// One player action, for replaying recorded games or fuzzing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Move { x: u8, y: u8 },
+    Explore { x: u8, y: u8 },
+    Bury { x: u8, y: u8, amount: u16 },
+    Dig { x: u8, y: u8 },
+    Descend,
+    UseItem { item: u8, x: u8, y: u8 },
+}
+
+/// Result of a replayed `Action`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Move(MoveResult),
+    Explore(ExploreOutcome),
+    Bury(BuryResult),
+    Dig(DigOutcome),
+    Descend(DescendOutcome),
+    UseItem(UseItemResult),
+}
+
+/// Plaintext counterpart of a game's encrypted state. Maps and buried
+/// layers are indexed by level; each player acts on their own level's.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    pub config: MapConfig,
+    pub maps: Vec<MapMatrix>,
+    pub buried: Vec<BuriedLayer>,
+    pub players: Vec<Player>,
+}
+
+impl Game {
+    /// `create_game` + `init_level` + `init_buried` for every level.
+    /// `seed` stands in for the cluster randomness init_map draws, so the
+    /// same seed rebuilds the same dungeon.
+    pub fn new(seed: u64, config: MapConfig) -> Self {
+        ArcisRNG::reseed(seed);
+        let maps = (0..config.levels)
+            .map(|level| init_map(config.clone(), level).to_arcis())
+            .collect();
+        let buried = (0..config.levels)
+            .map(|_| init_buried_layer().to_arcis())
+            .collect();
+        Game {
+            config,
+            maps,
+            buried,
+            players: Vec::new(),
+        }
+    }
+
+    pub fn register_player(&mut self) -> PlayerId {
+        let (state, visited) = register_player();
+        self.players.push(Player {
+            state: state.to_arcis(),
+            visited: visited.to_arcis(),
+        });
+        self.players.len() - 1
+    }
+
+    pub fn move_player(&mut self, id: PlayerId, x: u8, y: u8) -> Result<MoveResult, Aborted> {
+        let input = MoveInput {
+            target_x: x,
+            target_y: y,
+        };
+        let state = self.players[id].state.clone();
+        let map = self.maps[self.level(id)].clone();
+        let config = self.config.clone();
+
+        let (result, state) = run(|| {
+            move_player(
+                Shared.from_arcis(input),
+                Mxe.from_arcis(state),
+                Mxe.from_arcis(map),
+                config,
+                Shared,
+            )
+        })?;
+
+        self.players[id].state = state.to_arcis();
+        Ok(result.to_arcis())
+    }
+
+    pub fn explore(&mut self, id: PlayerId, x: u8, y: u8) -> Result<ExploreOutcome, Aborted> {
+        let input = ExploreInput {
+            target_x: x,
+            target_y: y,
+        };
+        let player = self.players[id].clone();
+        let level = self.level(id);
+        let map = self.maps[level].clone();
+        let config = self.config.clone();
+
+        let (result, state, map, visited, treasure_claimed, trap_triggered, eliminated) = run(|| {
+            explore(
+                Shared.from_arcis(input),
+                Mxe.from_arcis(player.state),
+                Mxe.from_arcis(map),
+                Mxe.from_arcis(player.visited),
+                config,
+                Shared,
+            )
+        })?;
+
+        self.players[id].state = state.to_arcis();
+        self.players[id].visited = visited.to_arcis();
+        self.maps[level] = map.to_arcis();
+        Ok(ExploreOutcome {
+            result: result.to_arcis(),
+            treasure_claimed,
+            trap_triggered,
+            eliminated,
+        })
+    }
+
+    pub fn bury(&mut self, id: PlayerId, x: u8, y: u8, amount: u16) -> Result<BuryResult, Aborted> {
+        let input = BuryInput {
+            target_x: x,
+            target_y: y,
+            amount,
+        };
+        let state = self.players[id].state.clone();
+        let level = self.level(id);
+        let buried = self.buried[level].clone();
+        let config = self.config.clone();
+
+        let (result, state, buried) = run(|| {
+            bury(
+                Shared.from_arcis(input),
+                Mxe.from_arcis(state),
+                Mxe.from_arcis(buried),
+                config,
+                Shared,
+            )
+        })?;
+
+        self.players[id].state = state.to_arcis();
+        self.buried[level] = buried.to_arcis();
+        Ok(result.to_arcis())
+    }
+
+    pub fn dig(&mut self, id: PlayerId, x: u8, y: u8) -> Result<DigOutcome, Aborted> {
+        let input = DigInput {
+            target_x: x,
+            target_y: y,
+        };
+        let state = self.players[id].state.clone();
+        let level = self.level(id);
+        let map = self.maps[level].clone();
+        let buried = self.buried[level].clone();
+        let config = self.config.clone();
+
+        let (result, state, map, buried, treasure_claimed, trap_triggered, eliminated) = run(|| {
+            dig(
+                Shared.from_arcis(input),
+                Mxe.from_arcis(state),
+                Mxe.from_arcis(map),
+                Mxe.from_arcis(buried),
+                config,
+                Shared,
+            )
+        })?;
+
+        self.players[id].state = state.to_arcis();
+        self.maps[level] = map.to_arcis();
+        self.buried[level] = buried.to_arcis();
+        Ok(DigOutcome {
+            result: result.to_arcis(),
+            treasure_claimed,
+            trap_triggered,
+            eliminated,
+        })
+    }
+
+    pub fn descend(&mut self, id: PlayerId) -> Result<DescendOutcome, Aborted> {
+        let player = self.players[id].clone();
+        let map = self.maps[self.level(id)].clone();
+        let config = self.config.clone();
+
+        let (result, state, visited, descended) = run(|| {
+            descend(
+                Mxe.from_arcis(player.state),
+                Mxe.from_arcis(map),
+                Mxe.from_arcis(player.visited),
+                config,
+                Shared,
+            )
+        })?;
+
+        self.players[id].state = state.to_arcis();
+        self.players[id].visited = visited.to_arcis();
+        Ok(DescendOutcome {
+            result: result.to_arcis(),
+            descended,
+        })
+    }
+
+    /// `(x, y)` only matters for the shovel
+    pub fn use_item(&mut self, id: PlayerId, item: u8, x: u8, y: u8) -> Result<UseItemResult, Aborted> {
+        let input = UseItemInput {
+            item,
+            target_x: x,
+            target_y: y,
+        };
+        let state = self.players[id].state.clone();
+        let level = self.level(id);
+        let map = self.maps[level].clone();
+        let config = self.config.clone();
+
+        let (result, state, map) = run(|| {
+            use_item(
+                Shared.from_arcis(input),
+                Mxe.from_arcis(state),
+                Mxe.from_arcis(map),
+                config,
+                Shared,
+            )
+        })?;
+
+        self.players[id].state = state.to_arcis();
+        self.maps[level] = map.to_arcis();
+        Ok(result.to_arcis())
+    }
+
+    /// Final gold as revealed by `reveal_standings`
+    pub fn reveal_standings(&self, id: PlayerId) -> u16 {
+        reveal_standings(Mxe.from_arcis(self.players[id].state.clone()))
+    }
+
+    /// Apply one recorded action
+    pub fn apply(&mut self, id: PlayerId, action: Action) -> Result<Outcome, Aborted> {
+        match action {
+            Action::Move { x, y } => self.move_player(id, x, y).map(Outcome::Move),
+            Action::Explore { x, y } => self.explore(id, x, y).map(Outcome::Explore),
+            Action::Bury { x, y, amount } => self.bury(id, x, y, amount).map(Outcome::Bury),
+            Action::Dig { x, y } => self.dig(id, x, y).map(Outcome::Dig),
+            Action::Descend => self.descend(id).map(Outcome::Descend),
+            Action::UseItem { item, x, y } => self.use_item(id, item, x, y).map(Outcome::UseItem),
+        }
+    }
+
+    /// Tile at (x, y) on the surface, using the same row-major layout as the circuits
+    pub fn tile(&self, x: u8, y: u8) -> MapTile {
+        self.tile_on(0, x, y)
+    }
+
+    /// Tile at (x, y) on `level`
+    pub fn tile_on(&self, level: u8, x: u8, y: u8) -> MapTile {
+        self.maps[level as usize].tiles[self.index(x, y)]
+    }
+
+    /// Loot buried at (x, y) on the surface
+    pub fn loot(&self, x: u8, y: u8) -> u16 {
+        self.buried[0].loot[self.index(x, y)]
+    }
+
+    /// Level the player is on — as on-chain, it picks the map they act on
+    fn level(&self, id: PlayerId) -> usize {
+        self.players[id].state.level as usize
+    }
+
+    fn index(&self, x: u8, y: u8) -> usize {
+        y as usize * self.config.width as usize + x as usize
+    }
+}
+
+impl MapConfig {
+    /// The 10x10 layout the original game shipped with
+    pub fn classic() -> Self {
+        MapConfig {
+            width: 10,
+            height: 10,
+            treasure_count: 15,
+            trap_count: 10,
+            treasure_min: 5,
+            treasure_max: 50,
+            trap_min: 5,
+            trap_max: 30,
+            levels: 1,
+            wall_count: 0,
+            spring_count: 0,
+            portal_pairs: 0,
+            item_count: 0,
+        }
+    }
+}
+
+/// Run a circuit, turning a panic into `Aborted`
+fn run<T>(circuit: impl FnOnce() -> T) -> Result<T, Aborted> {
+    panic::catch_unwind(AssertUnwindSafe(circuit)).map_err(|_| Aborted)
+}
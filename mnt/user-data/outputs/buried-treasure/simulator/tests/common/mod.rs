@@ -0,0 +1,25 @@
+// Seed search shared by the simulator tests and the program tests, which
+// include this file by path
+
+#![allow(dead_code)]
+
+use buried_treasure_simulator::{Game, MapConfig};
+
+/// First tile of `tile_type` next to the (0,0) spawn, if any
+pub fn spawn_neighbour(game: &Game, tile_type: u8) -> Option<(u8, u8)> {
+    [(1, 0), (0, 1), (1, 1)]
+        .into_iter()
+        .find(|&(x, y)| game.tile(x, y).tile_type == tile_type)
+}
+
+/// First of `seeds` whose dungeon puts a `tile_type` next to spawn, and
+/// where that tile is
+pub fn seed_with_spawn_neighbour(
+    seeds: impl IntoIterator<Item = u64>,
+    config: &MapConfig,
+    tile_type: u8,
+) -> Option<(u64, (u8, u8))> {
+    seeds.into_iter().find_map(|seed| {
+        spawn_neighbour(&Game::new(seed, config.clone()), tile_type).map(|pos| (seed, pos))
+    })
+}
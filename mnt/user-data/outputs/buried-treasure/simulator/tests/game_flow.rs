@@ -0,0 +1,583 @@
+use buried_treasure_simulator::{
+    Action, Game, MapConfig, Outcome, ITEM_MAP_FRAGMENT, ITEM_POTION, ITEM_SHOVEL,
+    ITEM_TRAP_DETECTOR, MAX_HEALTH, NO_ITEM, STATUS_BLOCKED, STATUS_DEAD, STATUS_INVALID_AMOUNT,
+    STATUS_NOT_ADJACENT, STATUS_NOT_ENOUGH_GOLD, STATUS_NO_ITEM, STATUS_NO_STAIRS, STATUS_NO_WALL,
+    STATUS_OK, STATUS_OUT_OF_BOUNDS,
+};
+
+mod common;
+
+use common::{seed_with_spawn_neighbour, spawn_neighbour};
+
+/// Seed whose map puts a `tile_type` next to spawn
+fn game_with_neighbour(tile_type: u8) -> (Game, (u8, u8)) {
+    game_with_neighbour_in(MapConfig::classic(), tile_type)
+}
+
+fn game_with_neighbour_in(config: MapConfig, tile_type: u8) -> (Game, (u8, u8)) {
+    let (seed, pos) = seed_with_spawn_neighbour(1..10_000, &config, tile_type)
+        .expect("some seed places the tile next to spawn");
+    (Game::new(seed, config), pos)
+}
+
+/// Classic map plus walls, springs and portals
+fn landmarks() -> MapConfig {
+    MapConfig {
+        wall_count: 8,
+        spring_count: 4,
+        portal_pairs: 2,
+        ..MapConfig::classic()
+    }
+}
+
+#[test]
+fn init_map_places_configured_counts() {
+    for (width, height) in [(8, 8), (10, 10), (12, 12)] {
+        let config = MapConfig {
+            width,
+            height,
+            ..MapConfig::classic()
+        };
+        let game = Game::new(42, config.clone());
+
+        let mut treasures = 0;
+        let mut traps = 0;
+        for y in 0..height {
+            for x in 0..width {
+                let tile = game.tile(x, y);
+                match tile.tile_type {
+                    1 => {
+                        treasures += 1;
+                        assert!((config.treasure_min..=config.treasure_max).contains(&tile.value));
+                    }
+                    2 => {
+                        traps += 1;
+                        assert!((config.trap_min..=config.trap_max).contains(&tile.value));
+                    }
+                    _ => assert_eq!(tile.value, 0),
+                }
+            }
+        }
+
+        assert_eq!(treasures, config.treasure_count);
+        assert_eq!(traps, config.trap_count);
+        assert_eq!(game.tile(0, 0).tile_type, 0, "spawn tile stays empty");
+    }
+}
+
+#[test]
+fn init_map_fills_even_a_full_map_exactly() {
+    // Every non-spawn tile taken — the densest config create_game accepts
+    let config = MapConfig {
+        width: 8,
+        height: 8,
+        treasure_count: 40,
+        trap_count: 23,
+        ..MapConfig::classic()
+    };
+    for seed in 0..20 {
+        let game = Game::new(seed, config.clone());
+        let count = |tile_type| game.maps[0].tiles.iter().filter(|t| t.tile_type == tile_type).count();
+        assert_eq!((count(1), count(2)), (40, 23));
+        assert_eq!(game.tile(0, 0).tile_type, 0);
+        assert!(game.maps[0].tiles[64..].iter().all(|t| t.tile_type == 0), "nothing off the map");
+    }
+}
+
+#[test]
+fn init_map_is_deterministic_per_seed() {
+    assert_eq!(
+        Game::new(7, MapConfig::classic()),
+        Game::new(7, MapConfig::classic())
+    );
+    assert_ne!(
+        Game::new(7, MapConfig::classic()).maps,
+        Game::new(8, MapConfig::classic()).maps
+    );
+}
+
+#[test]
+fn deeper_levels_are_richer_and_linked_by_stairs() {
+    let config = MapConfig {
+        levels: 3,
+        ..MapConfig::classic()
+    };
+    let game = Game::new(42, config.clone());
+    assert_eq!(game.maps.len(), 3);
+    assert_eq!(game.buried.len(), 3);
+
+    for (level, map) in game.maps.iter().enumerate() {
+        let scale = level as u16 + 1;
+        let count = |tile_type| map.tiles.iter().filter(|t| t.tile_type == tile_type).count();
+        assert_eq!((count(1), count(2)), (15, 10));
+        assert_eq!(count(3), if level < 2 { 1 } else { 0 }, "no stairs below the deepest level");
+        for tile in &map.tiles {
+            match tile.tile_type {
+                1 => assert!((5 * scale..=50 * scale).contains(&tile.value)),
+                2 => assert!((5 * scale..=30 * scale).contains(&tile.value)),
+                _ => assert_eq!(tile.value, 0),
+            }
+        }
+        assert_eq!(map.tiles[0].tile_type, 0, "every level's spawn stays empty");
+    }
+}
+
+#[test]
+fn init_map_places_walls_springs_and_linked_portals() {
+    for seed in 0..20 {
+        let game = Game::new(seed, landmarks());
+        let tiles = &game.maps[0].tiles;
+        let count = |tile_type| tiles.iter().filter(|t| t.tile_type == tile_type).count();
+        assert_eq!((count(1), count(2), count(4), count(5), count(6)), (15, 10, 8, 4, 4));
+
+        for (index, tile) in tiles.iter().enumerate() {
+            match tile.tile_type {
+                5 => assert!((10..=40).contains(&tile.value)),
+                6 => {
+                    let twin = tile.value as usize;
+                    assert_ne!(twin, index);
+                    assert_eq!(tiles[twin].tile_type, 6);
+                    assert_eq!(tiles[twin].value as usize, index, "portals come in pairs");
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[test]
+fn walls_block_moves_and_survive_digging() {
+    let (mut game, (x, y)) = game_with_neighbour_in(landmarks(), 4);
+    let id = game.register_player();
+
+    let moved = game.move_player(id, x, y).unwrap();
+    assert_eq!((moved.status, moved.new_x, moved.new_y), (STATUS_BLOCKED, 0, 0));
+
+    let explored = game.explore(id, x, y).unwrap();
+    assert_eq!((explored.result.tile_type, explored.result.value), (4, 0));
+
+    let dug = game.dig(id, x, y).unwrap();
+    assert_eq!(dug.result.found_type, 4);
+    assert_eq!(game.tile(x, y).tile_type, 4, "walls can't be dug away");
+}
+
+#[test]
+fn portals_lead_to_their_twin() {
+    let (mut game, (x, y)) = game_with_neighbour_in(landmarks(), 6);
+    let twin = game.tile(x, y).value;
+    let (tx, ty) = ((twin % 10) as u8, (twin / 10) as u8);
+    let id = game.register_player();
+
+    let explored = game.explore(id, x, y).unwrap();
+    assert_eq!((explored.result.tile_type, explored.result.value), (6, twin));
+
+    let dug = game.dig(id, x, y).unwrap();
+    assert_eq!(dug.result.found_type, 6);
+    assert_eq!(game.tile(x, y).tile_type, 6, "portals can't be dug away");
+
+    let moved = game.move_player(id, x, y).unwrap();
+    assert_eq!((moved.status, moved.new_x, moved.new_y), (STATUS_OK, tx, ty));
+    assert_eq!((game.players[id].state.x, game.players[id].state.y), (tx, ty));
+}
+
+#[test]
+fn moving_onto_traps_and_springs_leaves_health_alone() {
+    for tile_type in [2, 5] {
+        let (mut game, (x, y)) = game_with_neighbour_in(landmarks(), tile_type);
+        let id = game.register_player();
+        game.players[id].state.health = 50;
+
+        let moved = game.move_player(id, x, y).unwrap();
+        assert_eq!((moved.status, moved.new_x, moved.new_y), (STATUS_OK, x, y));
+        assert_eq!(game.players[id].state.health, 50);
+        assert_eq!(game.tile(x, y).tile_type, tile_type, "the tile is left for explore and dig");
+    }
+}
+
+#[test]
+fn springs_heal_up_to_the_cap() {
+    let (mut game, (x, y)) = game_with_neighbour_in(landmarks(), 5);
+    let heal = game.tile(x, y).value;
+    let first = game.register_player();
+    let second = game.register_player();
+
+    // Full health stays at the cap
+    let explored = game.explore(first, x, y).unwrap();
+    assert_eq!((explored.result.tile_type, explored.result.health), (5, MAX_HEALTH));
+
+    // Each player is healed once by exploring
+    game.players[second].state.health = 5;
+    for _ in 0..2 {
+        let explored = game.explore(second, x, y).unwrap();
+        assert_eq!(explored.result.health, 5 + heal);
+    }
+
+    // Digging heals what is missing and dries the spring up
+    game.players[first].state.health = MAX_HEALTH - 3;
+    let dug = game.dig(first, x, y).unwrap();
+    assert_eq!((dug.result.found_type, dug.result.health_restored), (5, 3));
+    assert_eq!(game.players[first].state.health, MAX_HEALTH);
+    assert_eq!(game.tile(x, y).tile_type, 0);
+}
+
+#[test]
+fn dig_picks_up_item_caches() {
+    let config = MapConfig {
+        item_count: 10,
+        ..MapConfig::classic()
+    };
+    let (mut game, (x, y)) = game_with_neighbour_in(config, 7);
+    let caches: Vec<_> = game.maps[0].tiles.iter().filter(|t| t.tile_type == 7).collect();
+    assert_eq!(caches.len(), 10);
+    assert!(caches.iter().all(|t| t.value < 4));
+
+    let kind = game.tile(x, y).value as u8;
+    let id = game.register_player();
+    assert_eq!(game.players[id].state.inventory, [0; 4]);
+
+    // Exploring shows the cache but only digging takes it
+    let explored = game.explore(id, x, y).unwrap();
+    assert_eq!((explored.result.tile_type, explored.result.value), (7, kind as u16));
+    assert_eq!(game.players[id].state.inventory, [0; 4]);
+
+    let dug = game.dig(id, x, y).unwrap();
+    assert_eq!((dug.result.found_type, dug.result.item), (7, kind));
+    assert_eq!(game.players[id].state.inventory[kind as usize], 1);
+    assert_eq!(game.tile(x, y).tile_type, 0);
+
+    let again = game.dig(id, x, y).unwrap();
+    assert_eq!(again.result.item, NO_ITEM);
+    assert_eq!(game.players[id].state.inventory[kind as usize], 1);
+}
+
+#[test]
+fn full_stack_leaves_the_cache_in_place() {
+    let config = MapConfig {
+        item_count: 10,
+        ..MapConfig::classic()
+    };
+    let (mut game, (x, y)) = game_with_neighbour_in(config, 7);
+    let kind = game.tile(x, y).value as u8;
+    let full = game.register_player();
+    game.players[full].state.inventory[kind as usize] = u8::MAX;
+
+    let dug = game.dig(full, x, y).unwrap();
+    assert_eq!((dug.result.status, dug.result.found_type), (STATUS_OK, 7));
+    assert_eq!(dug.result.item, NO_ITEM, "nothing was picked up");
+    assert_eq!(game.players[full].state.inventory[kind as usize], u8::MAX);
+    assert_eq!(game.tile(x, y).tile_type, 7);
+
+    // Still there for a player with room
+    let other = game.register_player();
+    assert_eq!(game.dig(other, x, y).unwrap().result.item, kind);
+    assert_eq!(game.players[other].state.inventory[kind as usize], 1);
+    assert_eq!(game.tile(x, y).tile_type, 0);
+}
+
+#[test]
+fn trap_detector_only_tells_whether_a_trap_is_adjacent() {
+    let traps_by_spawn = |game: &Game| {
+        [(1, 0), (0, 1), (1, 1)]
+            .into_iter()
+            .filter(|&(x, y)| game.tile(x, y).tile_type == 2)
+            .count()
+    };
+    for seed in 1..40 {
+        let mut game = Game::new(seed, MapConfig::classic());
+        let id = game.register_player();
+        game.players[id].state.inventory[ITEM_TRAP_DETECTOR as usize] = 1;
+
+        let result = game.use_item(id, ITEM_TRAP_DETECTOR, 0, 0).unwrap();
+        assert_eq!(result.status, STATUS_OK);
+        assert_eq!(result.value, (traps_by_spawn(&game) > 0) as u16, "seed {seed}");
+        assert_eq!(game.players[id].state.inventory[ITEM_TRAP_DETECTOR as usize], 0);
+
+        // Without another detector nothing happens
+        let before = game.clone();
+        let refused = game.use_item(id, ITEM_TRAP_DETECTOR, 0, 0).unwrap();
+        assert_eq!((refused.status, refused.value), (STATUS_NO_ITEM, 0));
+        assert_eq!(game, before);
+    }
+}
+
+#[test]
+fn shovel_fragment_and_potion_apply_their_effects() {
+    let config = MapConfig {
+        levels: 2,
+        ..landmarks()
+    };
+    let (mut game, (x, y)) = game_with_neighbour_in(config, 4);
+    let id = game.register_player();
+    game.players[id].state.inventory = [1, 0, 1, 1];
+
+    // The shovel only bites on walls
+    if let Some((ox, oy)) = [(1, 0), (0, 1), (1, 1)]
+        .into_iter()
+        .find(|&(ox, oy)| game.tile(ox, oy).tile_type != 4)
+    {
+        let refused = game.use_item(id, ITEM_SHOVEL, ox, oy).unwrap();
+        assert_eq!(refused.status, STATUS_NO_WALL);
+    }
+    let dug = game.use_item(id, ITEM_SHOVEL, x, y).unwrap();
+    assert_eq!(dug.status, STATUS_OK);
+    assert_eq!(game.tile(x, y).tile_type, 0, "the wall is gone");
+    assert_eq!(game.move_player(id, x, y).unwrap().status, STATUS_OK);
+
+    let stairs = game.maps[0].tiles.iter().position(|t| t.tile_type == 3).unwrap();
+    let fragment = game.use_item(id, ITEM_MAP_FRAGMENT, 0, 0).unwrap();
+    assert_eq!(fragment.value as usize, stairs);
+
+    game.players[id].state.health = 30;
+    let potion = game.use_item(id, ITEM_POTION, 0, 0).unwrap();
+    assert_eq!(potion.value, 80);
+    assert_eq!(game.players[id].state.health, 80);
+
+    assert_eq!(game.players[id].state.inventory, [0; 4]);
+    assert_eq!(game.use_item(id, 9, 0, 0).unwrap().status, STATUS_NO_ITEM);
+}
+
+#[test]
+fn descend_takes_the_stairs_to_the_next_level() {
+    let config = MapConfig {
+        levels: 2,
+        ..MapConfig::classic()
+    };
+    let (mut game, (x, y)) = game_with_neighbour_in(config, 3);
+    let id = game.register_player();
+
+    let before = game.clone();
+    let refused = game.descend(id).unwrap();
+    assert_eq!(refused.result.status, STATUS_NO_STAIRS);
+    assert!(!refused.descended);
+    assert_eq!(game, before, "no stairs at spawn");
+
+    let explored = game.explore(id, x, y).unwrap();
+    assert_eq!(explored.result.tile_type, 3);
+    game.dig(id, x, y).unwrap();
+    assert_eq!(game.tile(x, y).tile_type, 3, "stairs can't be dug away");
+    game.move_player(id, x, y).unwrap();
+
+    let outcome = game.descend(id).unwrap();
+    assert!(outcome.descended);
+    assert_eq!((outcome.result.status, outcome.result.level), (STATUS_OK, 1));
+    let state = &game.players[id].state;
+    assert_eq!((state.x, state.y, state.level), (0, 0, 1));
+    assert!(game.players[id].visited.visited.iter().all(|v| !v), "a fresh level to explore");
+
+    // Later actions land on level 1's map and buried layer
+    let surface = game.maps[0].clone();
+    let tile = game.tile_on(1, 1, 0);
+    let outcome = game.explore(id, 1, 0).unwrap();
+    assert_eq!((outcome.result.tile_type, outcome.result.value), (tile.tile_type, tile.value));
+    game.bury(id, 0, 1, 1).unwrap();
+    assert_eq!(game.buried[1].loot[10], 1);
+    assert_eq!(game.loot(0, 1), 0);
+    assert_eq!(game.maps[0], surface);
+
+    // The deepest level has no way down
+    assert_eq!(game.descend(id).unwrap().result.status, STATUS_NO_STAIRS);
+}
+
+#[test]
+fn register_player_starts_at_spawn() {
+    let mut game = Game::new(1, MapConfig::classic());
+    let id = game.register_player();
+    let state = &game.players[id].state;
+
+    assert_eq!((state.x, state.y, state.gold, state.health, state.level), (0, 0, 20, 100, 0));
+    assert!(game.players[id].visited.visited.iter().all(|v| !v));
+}
+
+#[test]
+fn move_player_requires_adjacent_in_bounds_target() {
+    let mut game = Game::new(1, MapConfig::classic());
+    let id = game.register_player();
+
+    let result = game.move_player(id, 1, 1).unwrap();
+    assert_eq!((result.status, result.new_x, result.new_y), (STATUS_OK, 1, 1));
+
+    let before = game.clone();
+    let far = game.move_player(id, 3, 3).unwrap();
+    assert_eq!((far.status, far.new_x, far.new_y), (STATUS_NOT_ADJACENT, 1, 1));
+    let stay = game.move_player(id, 1, 1).unwrap();
+    assert_eq!(stay.status, STATUS_NOT_ADJACENT, "staying put is not a move");
+    assert_eq!(game, before, "a rejected action changes nothing");
+
+    let mut edge = Game::new(1, MapConfig::classic());
+    let id = edge.register_player();
+    for step in 1..10 {
+        edge.move_player(id, step, 0).unwrap();
+    }
+    assert_eq!(edge.move_player(id, 10, 0).unwrap().status, STATUS_OUT_OF_BOUNDS);
+}
+
+#[test]
+fn explore_collects_treasure_once() {
+    let (mut game, (x, y)) = game_with_neighbour(1);
+    let value = game.tile(x, y).value;
+    let first = game.register_player();
+    let second = game.register_player();
+
+    let outcome = game.explore(first, x, y).unwrap();
+    assert_eq!((outcome.result.tile_type, outcome.result.value), (1, value));
+    assert!(outcome.treasure_claimed);
+    assert_eq!(game.players[first].state.gold, 20 + value);
+    assert_eq!(game.tile(x, y).tile_type, 0, "treasure is taken off the map");
+
+    let again = game.explore(first, x, y).unwrap();
+    assert!(!again.treasure_claimed);
+    assert_eq!(game.players[first].state.gold, 20 + value);
+
+    let other = game.explore(second, x, y).unwrap();
+    assert_eq!(other.result.tile_type, 0);
+    assert!(!other.treasure_claimed);
+    assert_eq!(game.players[second].state.gold, 20);
+}
+
+#[test]
+fn explore_trap_hurts_each_player_once() {
+    let (mut game, (x, y)) = game_with_neighbour(2);
+    let damage = game.tile(x, y).value;
+    let first = game.register_player();
+    let second = game.register_player();
+
+    for visit in 0..2 {
+        let outcome = game.explore(first, x, y).unwrap();
+        assert_eq!(outcome.result.tile_type, 2);
+        assert_eq!(outcome.trap_triggered, visit == 0);
+        assert!(!outcome.eliminated);
+    }
+    assert_eq!(game.players[first].state.health, 100 - damage);
+
+    game.explore(second, x, y).unwrap();
+    assert_eq!(game.players[second].state.health, 100 - damage, "trap stays armed");
+}
+
+#[test]
+fn bury_then_dig_moves_loot_between_players() {
+    let mut game = Game::new(3, MapConfig::classic());
+    let burier = game.register_player();
+    let digger = game.register_player();
+    // Pick an empty neighbour so dig only finds the buried pile
+    let (x, y) = spawn_neighbour(&game, 0).expect("classic map leaves a free tile by spawn");
+
+    let result = game.bury(burier, x, y, 15).unwrap();
+    assert_eq!((result.status, result.new_gold), (STATUS_OK, 5));
+    assert_eq!(game.loot(x, y), 15);
+
+    let outcome = game.dig(digger, x, y).unwrap();
+    assert_eq!((outcome.result.found_type, outcome.result.total_value), (1, 15));
+    assert!(!outcome.treasure_claimed, "buried loot is not a map treasure");
+    assert_eq!(game.players[digger].state.gold, 35);
+    assert_eq!(game.loot(x, y), 0);
+
+    let empty = game.dig(digger, x, y).unwrap();
+    assert_eq!((empty.result.found_type, empty.result.total_value), (0, 0));
+}
+
+#[test]
+fn bury_rejects_invalid_amounts() {
+    let mut game = Game::new(3, MapConfig::classic());
+    let id = game.register_player();
+
+    let before = game.clone();
+    assert_eq!(game.bury(id, 1, 0, 0).unwrap().status, STATUS_INVALID_AMOUNT);
+    assert_eq!(game.bury(id, 1, 0, 21).unwrap().status, STATUS_NOT_ENOUGH_GOLD);
+    assert_eq!(game.bury(id, 5, 5, 1).unwrap().status, STATUS_NOT_ADJACENT);
+    assert_eq!(game, before);
+}
+
+#[test]
+fn rejected_explore_and_dig_touch_nothing() {
+    let mut game = Game::new(3, MapConfig::classic());
+    let id = game.register_player();
+    // Loot buried elsewhere must survive a rejected dig
+    game.move_player(id, 1, 1).unwrap();
+    game.bury(id, 0, 0, 5).unwrap();
+    let before = game.clone();
+
+    let explored = game.explore(id, 9, 9).unwrap();
+    assert_eq!(explored.result.status, STATUS_NOT_ADJACENT);
+    assert_eq!((explored.result.tile_type, explored.result.value), (0, 0));
+    assert!(!explored.treasure_claimed && !explored.trap_triggered && !explored.eliminated);
+
+    let dug = game.dig(id, 9, 9).unwrap();
+    assert_eq!(dug.result.status, STATUS_NOT_ADJACENT);
+    assert_eq!((dug.result.found_type, dug.result.total_value), (0, 0));
+    assert_eq!(game, before);
+}
+
+#[test]
+fn dig_consumes_base_tile() {
+    let (mut game, (x, y)) = game_with_neighbour(1);
+    let value = game.tile(x, y).value;
+    let id = game.register_player();
+
+    let outcome = game.dig(id, x, y).unwrap();
+    assert!(outcome.treasure_claimed);
+    assert_eq!(outcome.result.total_value, value);
+    assert_eq!(game.tile(x, y).tile_type, 0);
+
+    let again = game.dig(id, x, y).unwrap();
+    assert!(!again.treasure_claimed);
+    assert_eq!(game.players[id].state.gold, 20 + value);
+}
+
+#[test]
+fn dead_players_cannot_act() {
+    let config = MapConfig {
+        trap_min: 100,
+        trap_max: 100,
+        ..MapConfig::classic()
+    };
+    let (mut game, (x, y)) = game_with_neighbour_in(config, 2);
+    let id = game.register_player();
+
+    let outcome = game.dig(id, x, y).unwrap();
+    assert!(outcome.trap_triggered && outcome.eliminated);
+    assert_eq!(outcome.result.health_lost, 100);
+
+    let before = game.clone();
+    assert_eq!(game.move_player(id, 1, 1).unwrap().status, STATUS_DEAD);
+    assert_eq!(game.explore(id, 1, 1).unwrap().result.status, STATUS_DEAD);
+    assert_eq!(game.bury(id, 1, 1, 1).unwrap().status, STATUS_DEAD);
+    assert_eq!(game.dig(id, 1, 1).unwrap().result.status, STATUS_DEAD);
+    assert_eq!(game, before);
+}
+
+#[test]
+fn replay_matches_direct_calls() {
+    let actions = [
+        Action::Explore { x: 1, y: 1 },
+        Action::Move { x: 1, y: 1 },
+        Action::Bury { x: 2, y: 2, amount: 5 },
+        Action::Dig { x: 2, y: 2 },
+        Action::Move { x: 2, y: 2 },
+    ];
+
+    let mut replayed = Game::new(11, MapConfig::classic());
+    let id = replayed.register_player();
+    let outcomes: Vec<_> = actions
+        .iter()
+        .map(|&action| replayed.apply(id, action))
+        .collect();
+
+    let mut direct = Game::new(11, MapConfig::classic());
+    let id = direct.register_player();
+    assert_eq!(outcomes[0], direct.explore(id, 1, 1).map(Outcome::Explore));
+    assert_eq!(outcomes[1], direct.move_player(id, 1, 1).map(Outcome::Move));
+    assert_eq!(outcomes[2], direct.bury(id, 2, 2, 5).map(Outcome::Bury));
+    assert_eq!(outcomes[3], direct.dig(id, 2, 2).map(Outcome::Dig));
+    assert_eq!(outcomes[4], direct.move_player(id, 2, 2).map(Outcome::Move));
+    assert_eq!(replayed, direct);
+}
+
+#[test]
+fn reveal_standings_reports_gold() {
+    let (mut game, (x, y)) = game_with_neighbour(1);
+    let value = game.tile(x, y).value;
+    let id = game.register_player();
+    game.explore(id, x, y).unwrap();
+
+    assert_eq!(game.reveal_standings(id), 20 + value);
+}
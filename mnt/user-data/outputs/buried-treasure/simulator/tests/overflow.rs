@@ -0,0 +1,85 @@
+// Gold and loot limits at u16::MAX
+
+use buried_treasure_simulator::{Game, MapConfig, MapTile, STATUS_OK, STATUS_PILE_FULL};
+
+fn rich_game() -> (Game, usize) {
+    let mut game = Game::new(5, MapConfig::classic());
+    let id = game.register_player();
+    game.players[id].state.gold = u16::MAX;
+    (game, id)
+}
+
+#[test]
+fn bury_refuses_to_overflow_a_pile() {
+    let (mut game, id) = rich_game();
+
+    let full = game.bury(id, 1, 0, u16::MAX - 10).unwrap();
+    assert_eq!(full.status, STATUS_OK);
+    assert_eq!(game.loot(1, 0), u16::MAX - 10);
+
+    game.players[id].state.gold = u16::MAX;
+    let overflow = game.bury(id, 1, 0, 11).unwrap();
+    assert_eq!((overflow.status, overflow.new_gold), (STATUS_PILE_FULL, u16::MAX));
+    assert_eq!(game.players[id].state.gold, u16::MAX, "gold is kept, not destroyed");
+    assert_eq!(game.loot(1, 0), u16::MAX - 10);
+
+    let topped = game.bury(id, 1, 0, 10).unwrap();
+    assert_eq!(topped.status, STATUS_OK);
+    assert_eq!(game.loot(1, 0), u16::MAX);
+}
+
+#[test]
+fn dig_saturates_gold() {
+    let (mut game, id) = rich_game();
+    game.bury(id, 1, 0, 1_000).unwrap();
+    game.maps[0].tiles[1] = MapTile {
+        tile_type: 1,
+        value: 500,
+    };
+
+    let outcome = game.dig(id, 1, 0).unwrap();
+    assert_eq!(outcome.result.total_value, 1_500);
+    assert_eq!(game.players[id].state.gold, u16::MAX);
+}
+
+#[test]
+fn dig_saturates_combined_layers() {
+    let mut game = Game::new(5, MapConfig::classic());
+    let id = game.register_player();
+    game.buried[0].loot[1] = u16::MAX;
+    game.maps[0].tiles[1] = MapTile {
+        tile_type: 1,
+        value: 50,
+    };
+
+    let outcome = game.dig(id, 1, 0).unwrap();
+    assert_eq!(outcome.result.total_value, u16::MAX);
+    assert_eq!(game.players[id].state.gold, u16::MAX);
+}
+
+#[test]
+fn explore_saturates_gold() {
+    let (mut game, id) = rich_game();
+    game.maps[0].tiles[1] = MapTile {
+        tile_type: 1,
+        value: 50,
+    };
+
+    let outcome = game.explore(id, 1, 0).unwrap();
+    assert!(outcome.treasure_claimed);
+    assert_eq!(game.players[id].state.gold, u16::MAX);
+}
+
+#[test]
+fn trap_damage_floors_health_at_zero() {
+    let mut game = Game::new(5, MapConfig::classic());
+    let id = game.register_player();
+    game.maps[0].tiles[1] = MapTile {
+        tile_type: 2,
+        value: u16::MAX,
+    };
+
+    let outcome = game.explore(id, 1, 0).unwrap();
+    assert!(outcome.eliminated);
+    assert_eq!(game.players[id].state.health, 0);
+}
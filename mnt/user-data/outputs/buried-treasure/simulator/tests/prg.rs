@@ -0,0 +1,157 @@
+// Map generator test vectors: the layout init_map lays out for a given
+// ArcisRNG seed, so a change to the draws or to how they are spent shows
+// up here rather than as a silently different dungeon
+
+use buried_treasure_simulator::{Game, MapConfig, MapMatrix};
+
+/// Tile types as one glyph each: empty, treasure, trap, staircase, wall,
+/// spring, portal, item cache
+const GLYPHS: &[u8; 8] = b".TX>#~O?";
+
+fn rows(map: &MapMatrix, config: &MapConfig) -> Vec<String> {
+    let width = config.width as usize;
+    (0..config.height as usize)
+        .map(|y| {
+            (0..width)
+                .map(|x| GLYPHS[map.tiles[y * width + x].tile_type as usize] as char)
+                .collect()
+        })
+        .collect()
+}
+
+/// Values of the tiles that carry one, row-major: treasure amounts, trap
+/// damage, spring heals, portal twins and cache items
+fn values(map: &MapMatrix, config: &MapConfig) -> Vec<u16> {
+    let tiles = config.width as usize * config.height as usize;
+    map.tiles[..tiles]
+        .iter()
+        .filter(|tile| matches!(tile.tile_type, 1 | 2 | 5 | 6 | 7))
+        .map(|tile| tile.value)
+        .collect()
+}
+
+#[test]
+fn classic_map_for_seed_1() {
+    let config = MapConfig::classic();
+    let game = Game::new(1, config.clone());
+
+    assert_eq!(
+        rows(&game.maps[0], &config),
+        [
+            "..X...X...",
+            "..........",
+            "..........",
+            "T.T.TT.T.T",
+            ".....X.T..",
+            "...X......",
+            "...T.X..X.",
+            "T.T.X....T",
+            ".X.X...T..",
+            "....T.TX.T",
+        ]
+    );
+    assert_eq!(
+        values(&game.maps[0], &config),
+        [
+            7, 8, 22, 17, 8, 8, 34, 40, 28, 48, 10, 41, 24, 12, 12, 29, 5, 16, 11, 15, 33, 40, 21,
+            24, 10,
+        ]
+    );
+}
+
+/// Every tile kind on the smallest tier; the level below draws on from
+/// where the surface left the stream, at twice the value ranges
+#[test]
+fn two_level_map_for_seed_42() {
+    let config = MapConfig {
+        width: 8,
+        height: 8,
+        treasure_count: 6,
+        trap_count: 4,
+        levels: 2,
+        wall_count: 5,
+        spring_count: 2,
+        portal_pairs: 2,
+        item_count: 3,
+        ..MapConfig::classic()
+    };
+    let game = Game::new(42, config.clone());
+
+    assert_eq!(
+        rows(&game.maps[0], &config),
+        [
+            ".....~..",
+            ".X.O....",
+            ".#.....#",
+            "T..OT.#T",
+            "...>~.X.",
+            "X...XO??",
+            "O.?#...#",
+            "..TT...T",
+        ]
+    );
+    assert_eq!(
+        values(&game.maps[0], &config),
+        [28, 28, 27, 23, 11, 16, 30, 37, 26, 19, 22, 48, 0, 3, 45, 1, 7, 31, 6]
+    );
+
+    assert_eq!(
+        rows(&game.maps[1], &config),
+        [
+            ".?X.TO..",
+            "..T...OX",
+            "......O#",
+            "O#...#X.",
+            "....T.T#",
+            "#~......",
+            "..XT.??.",
+            "~......T",
+        ]
+    );
+    assert_eq!(
+        values(&game.maps[1], &config),
+        [0, 60, 100, 24, 82, 22, 55, 14, 5, 43, 17, 82, 33, 12, 28, 3, 3, 38, 35]
+    );
+}
+
+#[test]
+fn large_map_for_seed_deadbeef() {
+    let config = MapConfig {
+        width: 12,
+        height: 12,
+        ..MapConfig::classic()
+    };
+    let game = Game::new(0xDEAD_BEEF, config.clone());
+
+    assert_eq!(
+        rows(&game.maps[0], &config),
+        [
+            "............",
+            "..T......T..",
+            "X.T.........",
+            ".X.......X..",
+            "....T....X..",
+            "....TTT..TXT",
+            "......TX....",
+            "X...X....T..",
+            "......X.....",
+            ".X...T......",
+            "....T...T...",
+            "T...........",
+        ]
+    );
+    assert_eq!(
+        values(&game.maps[0], &config),
+        [
+            31, 48, 24, 43, 8, 6, 46, 26, 39, 46, 44, 30, 11, 6, 33, 10, 17, 10, 33, 12, 10, 32, 12,
+            48, 24,
+        ]
+    );
+}
+
+#[test]
+fn seed_alone_decides_the_dungeon() {
+    let config = MapConfig::classic();
+    assert_eq!(Game::new(7, config.clone()), Game::new(7, config.clone()));
+    assert_ne!(Game::new(7, config.clone()).maps, Game::new(8, config).maps);
+}
@@ -0,0 +1,53 @@
+// ============================================================
+// ARCIUM RELAY
+// Loaded at ARCIUM_PROG_ID in place of the real Arcium program so the
+// mock MXE can deliver a callback the way the cluster does: as a CPI
+// whose top-level instruction belongs to the Arcium program
+// ============================================================
+//
+// Instruction data `[RELAY_TAG, callback data...]` with accounts
+// `[target program, callback accounts...]` invokes the target with the
+// rest. Everything else — the program's queue_computation and
+// init_comp_def CPIs — succeeds without doing anything.
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Prefix of a relayed callback, in place of an Anchor discriminator
+pub const RELAY_TAG: [u8; 8] = *b"relay-cb";
+
+entrypoint!(process_instruction);
+
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let Some(callback_data) = data.strip_prefix(&RELAY_TAG) else {
+        return Ok(());
+    };
+    let (target, callback_accounts) = accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let instruction = Instruction {
+        program_id: *target.key,
+        accounts: callback_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: callback_data.to_vec(),
+    };
+    invoke(&instruction, callback_accounts)
+}
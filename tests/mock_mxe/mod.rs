@@ -0,0 +1,1773 @@
+// ============================================================
+// BURIED TREASURE — MOCK MXE
+// Local stand-in for the Arcium cluster: runs the program in LiteSVM,
+// executes each queued computation with the plaintext simulator and
+// delivers the signed output to the matching *_callback instruction
+// ============================================================
+//
+// Ciphertexts use an identity cipher: every field is its little-endian
+// value in a 32-byte slot, and nonces count up from 1. That keeps the
+// callbacks byte-for-byte realistic in shape while letting the harness
+// decode exactly what the program persisted and feed it back into the
+// simulator for the next computation.
+//
+// Expects `anchor build` output in target/deploy and the Arcium program
+// in artifacts/arcium_program.so (see fetch-arcium-program.sh).
+// `MockMxe::with_arcium_relay` swaps the Arcium program for
+// tests/arcium_relay, built with `cargo build-sbf`, so failure reports can
+// arrive through ARCIUM_PROG_ID the way the cluster sends them. The .so
+// files are read at runtime; when one is missing the constructors return
+// None and the test skips.
+
+#![allow(dead_code)]
+
+use anchor_lang::{
+    AccountDeserialize, AccountSerialize, AnchorSerialize, Discriminator, Event,
+    InstructionData, ToAccountMetas,
+};
+use arcium_anchor::prelude::*;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use buried_treasure::{
+    accounts, instruction, CooldownConfig, Economics, EncryptedBuriedLayer, EncryptedMap,
+    EncryptedPlayerState, EncryptedVisitedTiles, Game, GameConfig, GameError, GameResult,
+    PayoutCurve, PlayerAccount, RakeWithdrawn, WinningsClaimed, ID,
+};
+use buried_treasure_simulator as sim;
+use litesvm::{types::TransactionMetadata, LiteSVM};
+use solana_sdk::{
+    clock::Clock,
+    program_option::COption,
+    program_pack::Pack,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::get_associated_token_address;
+
+const PROGRAM_SO: &str = "target/deploy/buried_treasure.so";
+const ARCIUM_SO: &str = "artifacts/arcium_program.so";
+const RELAY_SO: &str = "target/deploy/arcium_relay.so";
+
+/// Reads a built program relative to the crate root, or says which one is
+/// missing and how to build it
+fn load_program(path: &str) -> Option<Vec<u8>> {
+    let full = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+    match std::fs::read(&full) {
+        Ok(bytes) => Some(bytes),
+        Err(err) => {
+            eprintln!(
+                "skipping: cannot read {} ({err}); build it as described under \
+                 Program Tests in ARCHITECTURE.md",
+                full.display()
+            );
+            None
+        }
+    }
+}
+
+/// arcium_relay's RELAY_TAG: the rest of the data is a callback to invoke
+const RELAY_TAG: [u8; 8] = *b"relay-cb";
+
+/// Arcium.toml cluster_offset
+const CLUSTER_OFFSET: u32 = 0;
+
+/// How a queued action failed
+#[derive(Debug)]
+pub enum Failure {
+    /// The program rejected the instruction before queueing
+    Rejected(TransactionError),
+    /// The circuit's `assert!` failed — no callback is delivered
+    Aborted,
+}
+
+/// `create_game` parameters
+pub struct GameOptions {
+    pub duration: i64,
+    pub turn_duration: i64,
+    pub config: GameConfig,
+    pub economics: Economics,
+    pub cooldown: CooldownConfig,
+}
+
+impl GameOptions {
+    /// No deadline, no turn order, free to join, no cooldown
+    pub fn new(config: GameConfig) -> Self {
+        GameOptions {
+            duration: 0,
+            turn_duration: 0,
+            config,
+            economics: free_economics(),
+            cooldown: CooldownConfig {
+                max_action_points: 0,
+                slots_per_point: 0,
+            },
+        }
+    }
+}
+
+/// A registered player: wallet plus the x25519 key results are sealed to
+pub struct TestPlayer {
+    pub wallet: Keypair,
+    pub client_key: [u8; 32],
+}
+
+/// Arcium PDAs of one computation: the head of its callback context and,
+/// with the sign PDA and pools, the tail of the context that queues it
+struct ArciumAccounts {
+    sign_pda_account: Pubkey,
+    comp_def_account: Pubkey,
+    mxe_account: Pubkey,
+    mempool_account: Pubkey,
+    executing_pool: Pubkey,
+    computation_account: Pubkey,
+    cluster_account: Pubkey,
+}
+
+/// `accounts::$ctx` with the Arcium accounts queue_computation needs
+/// filled in from `$arcium`
+macro_rules! queue_accounts {
+    ($ctx:ident { $($field:ident: $value:expr),* $(,)? }, $arcium:expr $(,)?) => {{
+        let arcium: ArciumAccounts = $arcium;
+        accounts::$ctx {
+            $($field: $value,)*
+            sign_pda_account: arcium.sign_pda_account,
+            mxe_account: arcium.mxe_account,
+            mempool_account: arcium.mempool_account,
+            executing_pool: arcium.executing_pool,
+            computation_account: arcium.computation_account,
+            comp_def_account: arcium.comp_def_account,
+            cluster_account: arcium.cluster_account,
+            pool_account: ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            clock_account: ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            system_program: system_program::ID,
+            arcium_program: ARCIUM_PROG_ID,
+        }
+    }};
+}
+
+pub struct MockMxe {
+    pub svm: LiteSVM,
+    pub authority: Keypair,
+    /// Key the mock cluster signs computation outputs with
+    cluster: Keypair,
+    next_offset: u64,
+    next_nonce: u128,
+    /// Every transaction that landed, oldest first
+    landed: Vec<TransactionMetadata>,
+    /// Every callback delivered, oldest first, for replay
+    delivered: Vec<Instruction>,
+    /// Drop every callback, as if the cluster never finalized anything
+    pub silent: bool,
+}
+
+impl MockMxe {
+    /// None, after saying why, when a program binary hasn't been built
+    pub fn new() -> Option<Self> {
+        let mut mxe = Self::with_arcium(ARCIUM_SO)?;
+        mxe.init_comp_defs();
+        Some(mxe)
+    }
+
+    /// The relay in place of the Arcium program: queueing is a no-op and
+    /// `fail_move` / `fail_registration` can deliver failure reports
+    pub fn with_arcium_relay() -> Option<Self> {
+        let mut mxe = Self::with_arcium(RELAY_SO)?;
+        mxe.install_comp_defs();
+        Some(mxe)
+    }
+
+    fn with_arcium(arcium_so: &str) -> Option<Self> {
+        let program = load_program(PROGRAM_SO)?;
+        let arcium = load_program(arcium_so)?;
+        let mut svm = LiteSVM::new();
+        svm.add_program(ID, &program);
+        svm.add_program(ARCIUM_PROG_ID, &arcium);
+
+        let authority = Keypair::new();
+        svm.airdrop(&authority.pubkey(), 100 * LAMPORTS_PER_SOL).unwrap();
+
+        let mut mxe = MockMxe {
+            svm,
+            authority,
+            cluster: Keypair::new(),
+            next_offset: 1,
+            next_nonce: 1,
+            landed: Vec::new(),
+            delivered: Vec::new(),
+            silent: false,
+        };
+        mxe.install_cluster();
+        Some(mxe)
+    }
+
+    // =========================================================
+    // GAME SETUP
+    // =========================================================
+
+    /// `create_game` + `init_buried` for a free, free-for-all game
+    pub fn create_game(
+        &mut self,
+        game_id: u64,
+        duration: i64,
+        config: GameConfig,
+        seed: u64,
+    ) -> Result<Pubkey, Failure> {
+        let options = GameOptions {
+            duration,
+            ..GameOptions::new(config)
+        };
+        self.create_game_with(game_id, options, seed)
+    }
+
+    /// `create_game`, `init_level` for every deeper level and `init_buried`
+    /// for every level, all computations finalized. `seed` is the
+    /// randomness the mock cluster draws inside init_map.
+    pub fn create_game_with(
+        &mut self,
+        game_id: u64,
+        options: GameOptions,
+        seed: u64,
+    ) -> Result<Pubkey, Failure> {
+        let GameOptions {
+            duration,
+            turn_duration,
+            config,
+            economics,
+            cooldown,
+        } = options;
+        let authority = self.authority.insecure_clone();
+        let game = game_pda(&authority.pubkey(), game_id);
+
+        let offset = self.offset();
+        self.send(
+            instruction::CreateGame {
+                computation_offset: offset,
+                game_id,
+                duration,
+                turn_duration,
+                config,
+                economics,
+                cooldown,
+            },
+            queue_accounts!(
+                CreateGame {
+                    game,
+                    encrypted_map: map_pda(&game, 0),
+                    authority: authority.pubkey(),
+                },
+                self.arcium("init_map", offset)
+            ),
+            &[&authority],
+        )?;
+
+        let simulated = sim::Game::new(seed, map_config(&config));
+        self.finish_init_map(&game, 0, offset, &simulated.maps[0]);
+
+        for level in 1..config.levels {
+            let offset = self.offset();
+            self.send(
+                instruction::InitLevel {
+                    computation_offset: offset,
+                    level,
+                },
+                queue_accounts!(
+                    InitLevel {
+                        game,
+                        encrypted_map: map_pda(&game, level),
+                        authority: authority.pubkey(),
+                    },
+                    self.arcium("init_map", offset)
+                ),
+                &[&authority],
+            )?;
+            self.finish_init_map(&game, level, offset, &simulated.maps[level as usize]);
+        }
+
+        for level in 0..config.levels {
+            let offset = self.offset();
+            self.send(
+                instruction::InitBuried {
+                    computation_offset: offset,
+                    level,
+                },
+                queue_accounts!(
+                    InitBuried {
+                        game,
+                        encrypted_buried_layer: buried_pda(&game, level),
+                        authority: authority.pubkey(),
+                    },
+                    self.arcium("init_buried_layer", offset)
+                ),
+                &[&authority],
+            )?;
+            self.finish_init_buried(&game, level, offset, &simulated.buried[level as usize]);
+        }
+
+        Ok(game)
+    }
+
+    /// `retry_level_init` for a level whose map never landed; `seed` is the
+    /// randomness the mock cluster draws this time
+    pub fn retry_level_init(&mut self, game: &Pubkey, level: u8, seed: u64) -> Result<(), Failure> {
+        let authority = self.authority.insecure_clone();
+        let config = self.game(game).config;
+        let offset = self.offset();
+        self.send(
+            instruction::RetryLevelInit {
+                computation_offset: offset,
+                level,
+            },
+            queue_accounts!(
+                RetryLevelInit {
+                    game: *game,
+                    encrypted_map: map_pda(game, level),
+                    authority: authority.pubkey(),
+                },
+                self.arcium("init_map", offset)
+            ),
+            &[&authority],
+        )?;
+        let simulated = sim::Game::new(seed, map_config(&config));
+        self.finish_init_map(game, level, offset, &simulated.maps[level as usize]);
+        Ok(())
+    }
+
+    /// `retry_buried_init` for a level whose buried layer never landed
+    pub fn retry_buried_init(&mut self, game: &Pubkey, level: u8) -> Result<(), Failure> {
+        let authority = self.authority.insecure_clone();
+        let config = self.game(game).config;
+        let offset = self.offset();
+        self.send(
+            instruction::RetryBuriedInit {
+                computation_offset: offset,
+                level,
+            },
+            queue_accounts!(
+                RetryBuriedInit {
+                    game: *game,
+                    encrypted_buried_layer: buried_pda(game, level),
+                    authority: authority.pubkey(),
+                },
+                self.arcium("init_buried_layer", offset)
+            ),
+            &[&authority],
+        )?;
+        // Buried layers start empty, whatever the seed
+        let simulated = sim::Game::new(0, map_config(&config));
+        self.finish_init_buried(game, level, offset, &simulated.buried[level as usize]);
+        Ok(())
+    }
+
+    /// Deliver init_map's output for one level
+    fn finish_init_map(&mut self, game: &Pubkey, level: u8, offset: u64, map: &sim::MapMatrix) {
+        let output = InitMapOutput {
+            field_0: self.seal_mxe(map_fields(map)),
+        };
+        let arcium = self.arcium("init_map", offset);
+        self.callback(
+            instruction::InitMapCallback {
+                output: self.sign(offset, output),
+            },
+            accounts::InitMapCallback {
+                arcium_program: ARCIUM_PROG_ID,
+                comp_def_account: arcium.comp_def_account,
+                mxe_account: arcium.mxe_account,
+                computation_account: arcium.computation_account,
+                cluster_account: arcium.cluster_account,
+                instructions_sysvar: sysvar::instructions::ID,
+                game: *game,
+                encrypted_map: map_pda(game, level),
+            },
+        );
+    }
+
+    /// Deliver init_buried_layer's output for one level
+    fn finish_init_buried(
+        &mut self,
+        game: &Pubkey,
+        level: u8,
+        offset: u64,
+        layer: &sim::BuriedLayer,
+    ) {
+        let output = InitBuriedLayerOutput {
+            field_0: self.seal_mxe(buried_fields(layer)),
+        };
+        let arcium = self.arcium("init_buried_layer", offset);
+        self.callback(
+            instruction::InitBuriedLayerCallback {
+                output: self.sign(offset, output),
+            },
+            accounts::InitBuriedLayerCallback {
+                arcium_program: ARCIUM_PROG_ID,
+                comp_def_account: arcium.comp_def_account,
+                mxe_account: arcium.mxe_account,
+                computation_account: arcium.computation_account,
+                cluster_account: arcium.cluster_account,
+                instructions_sysvar: sysvar::instructions::ID,
+                game: *game,
+                encrypted_buried_layer: buried_pda(game, level),
+            },
+        );
+    }
+
+    /// Funded wallet with a fresh client key
+    pub fn new_player(&mut self) -> TestPlayer {
+        self.new_player_with_lamports(10 * LAMPORTS_PER_SOL)
+    }
+
+    pub fn new_player_with_lamports(&mut self, lamports: u64) -> TestPlayer {
+        let wallet = Keypair::new();
+        self.svm.airdrop(&wallet.pubkey(), lamports).unwrap();
+        let client_key = wallet.pubkey().to_bytes();
+        TestPlayer { wallet, client_key }
+    }
+
+    /// Staked games pay the entry fee from the wallet's associated token account
+    pub fn register_player(&mut self, game: &Pubkey, player: &TestPlayer) -> Result<(), Failure> {
+        let offset = self.queue_registration(game, player)?;
+        self.finish_registration(game, &player.wallet.pubkey(), offset);
+        Ok(())
+    }
+
+    /// `register_player` whose computation the cluster reports failed.
+    /// Needs `with_arcium_relay`.
+    pub fn fail_registration(
+        &mut self,
+        game: &Pubkey,
+        player: &TestPlayer,
+    ) -> Result<TransactionMetadata, Failure> {
+        let wallet = player.wallet.pubkey();
+        let offset = self.queue_registration(game, player)?;
+        let arcium = self.arcium("register_player", offset);
+        let instruction = relayed(program_instruction(
+            instruction::RegisterPlayerCallback {
+                output: SignedComputationOutputs::Failure,
+            },
+            accounts::RegisterPlayerCallback {
+                arcium_program: ARCIUM_PROG_ID,
+                comp_def_account: arcium.comp_def_account,
+                mxe_account: arcium.mxe_account,
+                computation_account: arcium.computation_account,
+                cluster_account: arcium.cluster_account,
+                instructions_sysvar: sysvar::instructions::ID,
+                player_account: player_pda(game, &wallet),
+                game: *game,
+                encrypted_player_state: player_state_pda(game, &wallet),
+                encrypted_visited_tiles: visited_pda(game, &wallet),
+            },
+        ));
+        let payer = self.authority.insecure_clone();
+        self.send_instruction(instruction, &[&payer])
+    }
+
+    pub fn retry_registration(&mut self, game: &Pubkey, player: &TestPlayer) -> Result<(), Failure> {
+        let wallet = player.wallet.pubkey();
+        let offset = self.offset();
+        self.send(
+            instruction::RetryRegistration {
+                computation_offset: offset,
+            },
+            queue_accounts!(
+                RetryRegistration {
+                    player_account: player_pda(game, &wallet),
+                    encrypted_player_state: player_state_pda(game, &wallet),
+                    encrypted_visited_tiles: visited_pda(game, &wallet),
+                    game: *game,
+                    player: wallet,
+                },
+                self.arcium("register_player", offset)
+            ),
+            &[&player.wallet],
+        )?;
+        self.finish_registration(game, &wallet, offset);
+        Ok(())
+    }
+
+    fn queue_registration(&mut self, game: &Pubkey, player: &TestPlayer) -> Result<u64, Failure> {
+        let wallet = player.wallet.pubkey();
+        let stake = self.stake(game);
+        let offset = self.offset();
+        self.send(
+            instruction::RegisterPlayer {
+                computation_offset: offset,
+            },
+            queue_accounts!(
+                RegisterPlayer {
+                    player_account: player_pda(game, &wallet),
+                    encrypted_player_state: player_state_pda(game, &wallet),
+                    encrypted_visited_tiles: visited_pda(game, &wallet),
+                    game: *game,
+                    player: wallet,
+                    player_token_account: stake
+                        .map(|mint| get_associated_token_address(&wallet, &mint)),
+                    vault: stake.map(|_| vault_pda(game)),
+                    token_program: stake.map(|_| spl_token::ID),
+                },
+                self.arcium("register_player", offset)
+            ),
+            &[&player.wallet],
+        )?;
+        Ok(offset)
+    }
+
+    /// Deliver register_player's output
+    fn finish_registration(&mut self, game: &Pubkey, wallet: &Pubkey, offset: u64) {
+        let mut simulated = self.simulator(game, None);
+        let id = simulated.register_player();
+        let output = RegisterPlayerOutput {
+            field_0: RegisterPlayerOutputStruct0 {
+                field_0: self.seal_mxe(state_fields(&simulated.players[id].state)),
+                field_1: self.seal_mxe(visited_fields(&simulated.players[id].visited)),
+            },
+        };
+        let arcium = self.arcium("register_player", offset);
+        self.callback(
+            instruction::RegisterPlayerCallback {
+                output: self.sign(offset, output),
+            },
+            accounts::RegisterPlayerCallback {
+                arcium_program: ARCIUM_PROG_ID,
+                comp_def_account: arcium.comp_def_account,
+                mxe_account: arcium.mxe_account,
+                computation_account: arcium.computation_account,
+                cluster_account: arcium.cluster_account,
+                instructions_sysvar: sysvar::instructions::ID,
+                player_account: player_pda(game, wallet),
+                game: *game,
+                encrypted_player_state: player_state_pda(game, wallet),
+                encrypted_visited_tiles: visited_pda(game, wallet),
+            },
+        );
+    }
+
+    pub fn init_vault(&mut self, game: &Pubkey, mint: &Pubkey, entry_fee: u64) -> Result<(), Failure> {
+        let authority = self.authority.insecure_clone();
+        self.send(
+            instruction::InitVault { entry_fee },
+            accounts::InitVault {
+                game: *game,
+                mint: *mint,
+                vault: vault_pda(game),
+                authority: authority.pubkey(),
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            &[&authority],
+        )
+        .map(|_| ())
+    }
+
+    // =========================================================
+    // PLAYER ACTIONS
+    // =========================================================
+
+    pub fn move_player(
+        &mut self,
+        game: &Pubkey,
+        player: &TestPlayer,
+        x: u8,
+        y: u8,
+    ) -> Result<sim::MoveResult, Failure> {
+        let wallet = player.wallet.pubkey();
+        let level = self.player_account(game, &wallet).level;
+        let offset = self.offset();
+        let nonce = self.nonce();
+        self.send(
+            instruction::MovePlayer {
+                computation_offset: offset,
+                ciphertext_input: seal_input([x as u16, y as u16]),
+                pub_key: player.client_key,
+                nonce,
+            },
+            queue_accounts!(
+                MovePlayer {
+                    player_account: player_pda(game, &wallet),
+                    encrypted_player_state: player_state_pda(game, &wallet),
+                    encrypted_map: map_pda(game, level),
+                    game: *game,
+                    player: wallet,
+                },
+                self.arcium("move_player", offset)
+            ),
+            &[&player.wallet],
+        )?;
+
+        let mut simulated = self.simulator(game, Some(&wallet));
+        let result = simulated.move_player(0, x, y).map_err(|_| Failure::Aborted)?;
+        let output = MovePlayerOutput {
+            field_0: MovePlayerOutputStruct0 {
+                field_0: self.seal_shared(
+                    player,
+                    [result.status as u128, result.new_x as u128, result.new_y as u128],
+                ),
+                field_1: self.seal_mxe(state_fields(&simulated.players[0].state)),
+            },
+        };
+        let arcium = self.arcium("move_player", offset);
+        self.callback(
+            instruction::MoveCallback {
+                output: self.sign(offset, output),
+            },
+            accounts::MoveCallback {
+                arcium_program: ARCIUM_PROG_ID,
+                comp_def_account: arcium.comp_def_account,
+                mxe_account: arcium.mxe_account,
+                computation_account: arcium.computation_account,
+                cluster_account: arcium.cluster_account,
+                instructions_sysvar: sysvar::instructions::ID,
+                player_account: player_pda(game, &wallet),
+                game: *game,
+                encrypted_player_state: player_state_pda(game, &wallet),
+            },
+        );
+        Ok(result)
+    }
+
+    pub fn explore(
+        &mut self,
+        game: &Pubkey,
+        player: &TestPlayer,
+        x: u8,
+        y: u8,
+    ) -> Result<sim::ExploreOutcome, Failure> {
+        let wallet = player.wallet.pubkey();
+        let level = self.player_account(game, &wallet).level;
+        let offset = self.offset();
+        let nonce = self.nonce();
+        self.send(
+            instruction::Explore {
+                computation_offset: offset,
+                ciphertext_input: seal_input([x as u16, y as u16]),
+                pub_key: player.client_key,
+                nonce,
+            },
+            queue_accounts!(
+                Explore {
+                    player_account: player_pda(game, &wallet),
+                    encrypted_player_state: player_state_pda(game, &wallet),
+                    encrypted_map: map_pda(game, level),
+                    encrypted_visited_tiles: visited_pda(game, &wallet),
+                    game: *game,
+                    player: wallet,
+                },
+                self.arcium("explore", offset)
+            ),
+            &[&player.wallet],
+        )?;
+
+        let mut simulated = self.simulator(game, Some(&wallet));
+        let outcome = simulated.explore(0, x, y).map_err(|_| Failure::Aborted)?;
+        let result = &outcome.result;
+        let output = ExploreOutput {
+            field_0: ExploreOutputStruct0 {
+                field_0: self.seal_shared(
+                    player,
+                    [
+                        result.status as u128,
+                        result.tile_type as u128,
+                        result.value as u128,
+                        result.health as u128,
+                    ],
+                ),
+                field_1: self.seal_mxe(state_fields(&simulated.players[0].state)),
+                field_2: self.seal_mxe(map_fields(&simulated.maps[level as usize])),
+                field_3: self.seal_mxe(visited_fields(&simulated.players[0].visited)),
+                field_4: outcome.treasure_claimed,
+                field_5: outcome.trap_triggered,
+                field_6: outcome.eliminated,
+            },
+        };
+        let arcium = self.arcium("explore", offset);
+        self.callback(
+            instruction::ExploreCallback {
+                output: self.sign(offset, output),
+            },
+            accounts::ExploreCallback {
+                arcium_program: ARCIUM_PROG_ID,
+                comp_def_account: arcium.comp_def_account,
+                mxe_account: arcium.mxe_account,
+                computation_account: arcium.computation_account,
+                cluster_account: arcium.cluster_account,
+                instructions_sysvar: sysvar::instructions::ID,
+                player_account: player_pda(game, &wallet),
+                game: *game,
+                encrypted_player_state: player_state_pda(game, &wallet),
+                encrypted_map: map_pda(game, level),
+                encrypted_visited_tiles: visited_pda(game, &wallet),
+            },
+        );
+        Ok(outcome)
+    }
+
+    pub fn bury(
+        &mut self,
+        game: &Pubkey,
+        player: &TestPlayer,
+        x: u8,
+        y: u8,
+        amount: u16,
+    ) -> Result<sim::BuryResult, Failure> {
+        let wallet = player.wallet.pubkey();
+        let level = self.player_account(game, &wallet).level;
+        let offset = self.offset();
+        let nonce = self.nonce();
+        self.send(
+            instruction::Bury {
+                computation_offset: offset,
+                ciphertext_input: seal_input([x as u16, y as u16, amount]),
+                pub_key: player.client_key,
+                nonce,
+            },
+            queue_accounts!(
+                Bury {
+                    player_account: player_pda(game, &wallet),
+                    encrypted_player_state: player_state_pda(game, &wallet),
+                    encrypted_buried_layer: buried_pda(game, level),
+                    game: *game,
+                    player: wallet,
+                },
+                self.arcium("bury", offset)
+            ),
+            &[&player.wallet],
+        )?;
+
+        let mut simulated = self.simulator(game, Some(&wallet));
+        let result = simulated.bury(0, x, y, amount).map_err(|_| Failure::Aborted)?;
+        let output = BuryOutput {
+            field_0: BuryOutputStruct0 {
+                field_0: self.seal_shared(player, [result.status as u128, result.new_gold as u128]),
+                field_1: self.seal_mxe(state_fields(&simulated.players[0].state)),
+                field_2: self.seal_mxe(buried_fields(&simulated.buried[level as usize])),
+            },
+        };
+        let arcium = self.arcium("bury", offset);
+        self.callback(
+            instruction::BuryCallback {
+                output: self.sign(offset, output),
+            },
+            accounts::BuryCallback {
+                arcium_program: ARCIUM_PROG_ID,
+                comp_def_account: arcium.comp_def_account,
+                mxe_account: arcium.mxe_account,
+                computation_account: arcium.computation_account,
+                cluster_account: arcium.cluster_account,
+                instructions_sysvar: sysvar::instructions::ID,
+                player_account: player_pda(game, &wallet),
+                game: *game,
+                encrypted_player_state: player_state_pda(game, &wallet),
+                encrypted_buried_layer: buried_pda(game, level),
+            },
+        );
+        Ok(result)
+    }
+
+    pub fn dig(
+        &mut self,
+        game: &Pubkey,
+        player: &TestPlayer,
+        x: u8,
+        y: u8,
+    ) -> Result<sim::DigOutcome, Failure> {
+        let wallet = player.wallet.pubkey();
+        let level = self.player_account(game, &wallet).level;
+        let offset = self.offset();
+        let nonce = self.nonce();
+        self.send(
+            instruction::Dig {
+                computation_offset: offset,
+                ciphertext_input: seal_input([x as u16, y as u16]),
+                pub_key: player.client_key,
+                nonce,
+            },
+            queue_accounts!(
+                Dig {
+                    player_account: player_pda(game, &wallet),
+                    encrypted_player_state: player_state_pda(game, &wallet),
+                    encrypted_map: map_pda(game, level),
+                    encrypted_buried_layer: buried_pda(game, level),
+                    game: *game,
+                    player: wallet,
+                },
+                self.arcium("dig", offset)
+            ),
+            &[&player.wallet],
+        )?;
+
+        let mut simulated = self.simulator(game, Some(&wallet));
+        let outcome = simulated.dig(0, x, y).map_err(|_| Failure::Aborted)?;
+        let result = &outcome.result;
+        let output = DigOutput {
+            field_0: DigOutputStruct0 {
+                field_0: self.seal_shared(
+                    player,
+                    [
+                        result.status as u128,
+                        result.found_type as u128,
+                        result.total_value as u128,
+                        result.health_lost as u128,
+                        result.health_restored as u128,
+                        result.item as u128,
+                    ],
+                ),
+                field_1: self.seal_mxe(state_fields(&simulated.players[0].state)),
+                field_2: self.seal_mxe(map_fields(&simulated.maps[level as usize])),
+                field_3: self.seal_mxe(buried_fields(&simulated.buried[level as usize])),
+                field_4: outcome.treasure_claimed,
+                field_5: outcome.trap_triggered,
+                field_6: outcome.eliminated,
+            },
+        };
+        let arcium = self.arcium("dig", offset);
+        self.callback(
+            instruction::DigCallback {
+                output: self.sign(offset, output),
+            },
+            accounts::DigCallback {
+                arcium_program: ARCIUM_PROG_ID,
+                comp_def_account: arcium.comp_def_account,
+                mxe_account: arcium.mxe_account,
+                computation_account: arcium.computation_account,
+                cluster_account: arcium.cluster_account,
+                instructions_sysvar: sysvar::instructions::ID,
+                player_account: player_pda(game, &wallet),
+                game: *game,
+                encrypted_player_state: player_state_pda(game, &wallet),
+                encrypted_map: map_pda(game, level),
+                encrypted_buried_layer: buried_pda(game, level),
+            },
+        );
+        Ok(outcome)
+    }
+
+    pub fn descend(&mut self, game: &Pubkey, player: &TestPlayer) -> Result<sim::DescendOutcome, Failure> {
+        let wallet = player.wallet.pubkey();
+        let level = self.player_account(game, &wallet).level;
+        let below = (level + 1 < self.game(game).config.levels).then_some(level + 1);
+        let offset = self.offset();
+        let nonce = self.nonce();
+        self.send(
+            instruction::Descend {
+                computation_offset: offset,
+                pub_key: player.client_key,
+                nonce,
+            },
+            queue_accounts!(
+                Descend {
+                    player_account: player_pda(game, &wallet),
+                    encrypted_player_state: player_state_pda(game, &wallet),
+                    encrypted_map: map_pda(game, level),
+                    encrypted_visited_tiles: visited_pda(game, &wallet),
+                    next_map: below.map(|level| map_pda(game, level)),
+                    next_buried_layer: below.map(|level| buried_pda(game, level)),
+                    game: *game,
+                    player: wallet,
+                },
+                self.arcium("descend", offset)
+            ),
+            &[&player.wallet],
+        )?;
+
+        let mut simulated = self.simulator(game, Some(&wallet));
+        let outcome = simulated.descend(0).map_err(|_| Failure::Aborted)?;
+        let output = DescendOutput {
+            field_0: DescendOutputStruct0 {
+                field_0: self.seal_shared(
+                    player,
+                    [outcome.result.status as u128, outcome.result.level as u128],
+                ),
+                field_1: self.seal_mxe(state_fields(&simulated.players[0].state)),
+                field_2: self.seal_mxe(visited_fields(&simulated.players[0].visited)),
+                field_3: outcome.descended,
+            },
+        };
+        let arcium = self.arcium("descend", offset);
+        self.callback(
+            instruction::DescendCallback {
+                output: self.sign(offset, output),
+            },
+            accounts::DescendCallback {
+                arcium_program: ARCIUM_PROG_ID,
+                comp_def_account: arcium.comp_def_account,
+                mxe_account: arcium.mxe_account,
+                computation_account: arcium.computation_account,
+                cluster_account: arcium.cluster_account,
+                instructions_sysvar: sysvar::instructions::ID,
+                player_account: player_pda(game, &wallet),
+                game: *game,
+                encrypted_player_state: player_state_pda(game, &wallet),
+                encrypted_visited_tiles: visited_pda(game, &wallet),
+            },
+        );
+        Ok(outcome)
+    }
+
+    pub fn use_item(
+        &mut self,
+        game: &Pubkey,
+        player: &TestPlayer,
+        item: u8,
+        x: u8,
+        y: u8,
+    ) -> Result<sim::UseItemResult, Failure> {
+        let wallet = player.wallet.pubkey();
+        let level = self.player_account(game, &wallet).level;
+        let offset = self.offset();
+        let nonce = self.nonce();
+        self.send(
+            instruction::UseItem {
+                computation_offset: offset,
+                ciphertext_input: seal_input([item as u16, x as u16, y as u16]),
+                pub_key: player.client_key,
+                nonce,
+            },
+            queue_accounts!(
+                UseItem {
+                    player_account: player_pda(game, &wallet),
+                    encrypted_player_state: player_state_pda(game, &wallet),
+                    encrypted_map: map_pda(game, level),
+                    game: *game,
+                    player: wallet,
+                },
+                self.arcium("use_item", offset)
+            ),
+            &[&player.wallet],
+        )?;
+
+        let mut simulated = self.simulator(game, Some(&wallet));
+        let result = simulated.use_item(0, item, x, y).map_err(|_| Failure::Aborted)?;
+        let output = UseItemOutput {
+            field_0: UseItemOutputStruct0 {
+                field_0: self.seal_shared(
+                    player,
+                    [result.status as u128, result.item as u128, result.value as u128],
+                ),
+                field_1: self.seal_mxe(state_fields(&simulated.players[0].state)),
+                field_2: self.seal_mxe(map_fields(&simulated.maps[level as usize])),
+            },
+        };
+        let arcium = self.arcium("use_item", offset);
+        self.callback(
+            instruction::UseItemCallback {
+                output: self.sign(offset, output),
+            },
+            accounts::UseItemCallback {
+                arcium_program: ARCIUM_PROG_ID,
+                comp_def_account: arcium.comp_def_account,
+                mxe_account: arcium.mxe_account,
+                computation_account: arcium.computation_account,
+                cluster_account: arcium.cluster_account,
+                instructions_sysvar: sysvar::instructions::ID,
+                player_account: player_pda(game, &wallet),
+                game: *game,
+                encrypted_player_state: player_state_pda(game, &wallet),
+                encrypted_map: map_pda(game, level),
+            },
+        );
+        Ok(result)
+    }
+
+    /// Crank past the seat whose turn it is
+    pub fn skip_turn(&mut self, game: &Pubkey, current: &Pubkey) -> Result<TransactionMetadata, Failure> {
+        let payer = self.authority.insecure_clone();
+        self.send(
+            instruction::SkipTurn {},
+            accounts::SkipTurn {
+                game: *game,
+                current_player: player_pda(game, current),
+            },
+            &[&payer],
+        )
+    }
+
+    /// Sent by the authority: anyone may release a timed-out computation
+    pub fn abort_pending(
+        &mut self,
+        game: &Pubkey,
+        wallet: &Pubkey,
+    ) -> Result<TransactionMetadata, Failure> {
+        let payer = self.authority.insecure_clone();
+        self.send(
+            instruction::AbortPending {},
+            accounts::AbortPending {
+                player_account: player_pda(game, wallet),
+                game: *game,
+            },
+            &[&payer],
+        )
+    }
+
+    // =========================================================
+    // GAME END
+    // =========================================================
+
+    pub fn end_game(&mut self, game: &Pubkey, caller: &Keypair) -> Result<(), Failure> {
+        self.send(
+            instruction::EndGame {},
+            accounts::EndGame {
+                game: *game,
+                game_result: result_pda(game),
+                caller: caller.pubkey(),
+                system_program: system_program::ID,
+            },
+            &[caller],
+        )
+        .map(|_| ())
+    }
+
+    pub fn reveal_standing(&mut self, game: &Pubkey, wallet: &Pubkey) -> Result<u16, Failure> {
+        let payer = self.authority.insecure_clone();
+        let offset = self.offset();
+        self.send(
+            instruction::RevealStanding {
+                computation_offset: offset,
+            },
+            queue_accounts!(
+                RevealStanding {
+                    player_account: player_pda(game, wallet),
+                    encrypted_player_state: player_state_pda(game, wallet),
+                    game: *game,
+                    game_result: result_pda(game),
+                    payer: payer.pubkey(),
+                },
+                self.arcium("reveal_standings", offset)
+            ),
+            &[&payer],
+        )?;
+
+        let gold = self.simulator(game, Some(wallet)).reveal_standings(0);
+        let output = RevealStandingsOutput { field_0: gold };
+        let arcium = self.arcium("reveal_standings", offset);
+        self.callback(
+            instruction::RevealStandingsCallback {
+                output: self.sign(offset, output),
+            },
+            accounts::RevealStandingsCallback {
+                arcium_program: ARCIUM_PROG_ID,
+                comp_def_account: arcium.comp_def_account,
+                mxe_account: arcium.mxe_account,
+                computation_account: arcium.computation_account,
+                cluster_account: arcium.cluster_account,
+                instructions_sysvar: sysvar::instructions::ID,
+                player_account: player_pda(game, wallet),
+                game: *game,
+                game_result: result_pda(game),
+            },
+        );
+        Ok(gold)
+    }
+
+    pub fn reveal_unregistered(&mut self, game: &Pubkey, wallet: &Pubkey) -> Result<(), Failure> {
+        let payer = self.authority.insecure_clone();
+        self.send(
+            instruction::RevealUnregistered {},
+            accounts::RevealUnregistered {
+                player_account: player_pda(game, wallet),
+                encrypted_player_state: player_state_pda(game, wallet),
+                game: *game,
+                game_result: result_pda(game),
+            },
+            &[&payer],
+        )
+        .map(|_| ())
+    }
+
+    /// Returns the (lamports, tokens) paid out
+    pub fn claim_winnings(&mut self, game: &Pubkey, player: &TestPlayer) -> Result<(u64, u64), Failure> {
+        let wallet = player.wallet.pubkey();
+        let stake = self.stake(game);
+        let meta = self.send(
+            instruction::ClaimWinnings {},
+            accounts::ClaimWinnings {
+                game: *game,
+                game_result: result_pda(game),
+                player_account: player_pda(game, &wallet),
+                player: wallet,
+                vault: stake.map(|_| vault_pda(game)),
+                player_token_account: stake.map(|mint| get_associated_token_address(&wallet, &mint)),
+                token_program: stake.map(|_| spl_token::ID),
+            },
+            &[&player.wallet],
+        )?;
+        let claimed = &events::<WinningsClaimed>(&meta)[0];
+        Ok((claimed.lamports, claimed.tokens))
+    }
+
+    /// Returns the (lamports, tokens) withdrawn
+    pub fn withdraw_rake(&mut self, game: &Pubkey, caller: &Keypair) -> Result<(u64, u64), Failure> {
+        let stake = self.stake(game);
+        let meta = self.send(
+            instruction::WithdrawRake {},
+            accounts::WithdrawRake {
+                game: *game,
+                game_result: result_pda(game),
+                authority: caller.pubkey(),
+                vault: stake.map(|_| vault_pda(game)),
+                authority_token_account: stake
+                    .map(|mint| get_associated_token_address(&caller.pubkey(), &mint)),
+                token_program: stake.map(|_| spl_token::ID),
+            },
+            &[caller],
+        )?;
+        let withdrawn = &events::<RakeWithdrawn>(&meta)[0];
+        Ok((withdrawn.lamports, withdrawn.tokens))
+    }
+
+    // =========================================================
+    // STATE INSPECTION
+    // =========================================================
+
+    pub fn game(&self, game: &Pubkey) -> Game {
+        self.fetch(game)
+    }
+
+    /// The game's token mint, if staked
+    pub fn stake(&self, game: &Pubkey) -> Option<Pubkey> {
+        Some(self.game(game).mint).filter(|mint| *mint != Pubkey::default())
+    }
+
+    pub fn player_account(&self, game: &Pubkey, wallet: &Pubkey) -> PlayerAccount {
+        self.fetch(&player_pda(game, wallet))
+    }
+
+    pub fn game_result(&self, game: &Pubkey) -> GameResult {
+        self.fetch(&result_pda(game))
+    }
+
+    /// Decode the game's encrypted accounts back into simulator state.
+    /// With `wallet`, that player's state is loaded as player 0.
+    pub fn simulator(&self, game: &Pubkey, wallet: Option<&Pubkey>) -> sim::Game {
+        let config = map_config(&self.game(game).config);
+        let maps = (0..config.levels)
+            .map(|level| {
+                let map: EncryptedMap = self.load(&map_pda(game, level));
+                map_from(&map.ciphertexts)
+            })
+            .collect();
+        let buried = (0..config.levels)
+            .map(|level| {
+                let layer: EncryptedBuriedLayer = self.load(&buried_pda(game, level));
+                buried_from(&layer.ciphertexts)
+            })
+            .collect();
+
+        let players = wallet
+            .map(|wallet| {
+                let state: EncryptedPlayerState = self.load(&player_state_pda(game, wallet));
+                let visited: EncryptedVisitedTiles = self.load(&visited_pda(game, wallet));
+                sim::Player {
+                    state: state_from(&state.ciphertexts),
+                    visited: visited_from(&visited.ciphertexts),
+                }
+            })
+            .into_iter()
+            .collect();
+
+        sim::Game {
+            config,
+            maps,
+            buried,
+            players,
+        }
+    }
+
+    /// New SPL mint; tokens are handed out with `fund_tokens`
+    pub fn create_mint(&mut self) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        let state = spl_token::state::Mint {
+            mint_authority: COption::Some(self.authority.pubkey()),
+            supply: 0,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        self.set_packed_account(&mint, state);
+        mint
+    }
+
+    /// Give `wallet` an associated token account holding `amount` of `mint`
+    pub fn fund_tokens(&mut self, wallet: &Pubkey, mint: &Pubkey, amount: u64) -> Pubkey {
+        let address = get_associated_token_address(wallet, mint);
+        let state = spl_token::state::Account {
+            mint: *mint,
+            owner: *wallet,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        self.set_packed_account(&address, state);
+        address
+    }
+
+    pub fn token_balance(&self, address: &Pubkey) -> u64 {
+        let account = self.svm.get_account(address).expect("token account exists");
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+
+    /// Move the cluster clock forward
+    pub fn advance_clock(&mut self, seconds: i64) {
+        let mut clock: Clock = self.svm.get_sysvar();
+        clock.unix_timestamp += seconds;
+        self.svm.set_sysvar(&clock);
+    }
+
+    /// Events of type `E` across every landed transaction, in order
+    pub fn emitted<E: Event + Discriminator>(&self) -> Vec<E> {
+        self.landed.iter().flat_map(events::<E>).collect()
+    }
+
+    /// Move the cluster forward `slots` slots
+    pub fn advance_slots(&mut self, slots: u64) {
+        let mut clock: Clock = self.svm.get_sysvar();
+        clock.slot += slots;
+        self.svm.set_sysvar(&clock);
+    }
+
+    /// Deliver `explore_callback` signed by a key that is not the cluster's
+    pub fn forge_explore_callback(&mut self, game: &Pubkey, wallet: &Pubkey) -> Result<(), Failure> {
+        let mut simulated = self.simulator(game, Some(wallet));
+        simulated.players[0].state.gold = u16::MAX;
+        let output = ExploreOutput {
+            field_0: ExploreOutputStruct0 {
+                field_0: SharedEncryptedStruct {
+                    encryption_key: [0u8; 32],
+                    nonce: self.nonce(),
+                    ciphertexts: [seal(0), seal(1), seal(u16::MAX as u128), seal(100)],
+                },
+                field_1: self.seal_mxe(state_fields(&simulated.players[0].state)),
+                field_2: self.seal_mxe(map_fields(&simulated.maps[0])),
+                field_3: self.seal_mxe(visited_fields(&simulated.players[0].visited)),
+                field_4: true,
+                field_5: false,
+                field_6: false,
+            },
+        };
+
+        let offset = self.offset();
+        let forger = Keypair::new();
+        let signature = forger.sign_message(&signed_bytes(offset, &output));
+        let arcium = self.arcium("explore", offset);
+        self.try_callback(
+            instruction::ExploreCallback {
+                output: SignedComputationOutputs::Success(output, signature.into()),
+            },
+            accounts::ExploreCallback {
+                arcium_program: ARCIUM_PROG_ID,
+                comp_def_account: arcium.comp_def_account,
+                mxe_account: arcium.mxe_account,
+                computation_account: arcium.computation_account,
+                cluster_account: arcium.cluster_account,
+                instructions_sysvar: sysvar::instructions::ID,
+                player_account: player_pda(game, wallet),
+                game: *game,
+                encrypted_player_state: player_state_pda(game, wallet),
+                encrypted_map: map_pda(game, 0),
+                encrypted_visited_tiles: visited_pda(game, wallet),
+            },
+        )
+        .map(|_| ())
+    }
+
+    /// Re-deliver every `I` callback delivered so far, as anyone holding
+    /// the old signed outputs could
+    pub fn replay<I: Discriminator>(&mut self) -> Vec<Result<TransactionMetadata, Failure>> {
+        let payer = self.authority.insecure_clone();
+        let replays: Vec<Instruction> = self
+            .delivered
+            .iter()
+            .filter(|ix| ix.data.starts_with(&I::DISCRIMINATOR))
+            .cloned()
+            .collect();
+        replays
+            .into_iter()
+            .map(|ix| self.send_instruction(ix, &[&payer]))
+            .collect()
+    }
+
+    /// Report the player's pending move as failed, sent directly rather
+    /// than by the Arcium program
+    pub fn forge_move_failure(&mut self, game: &Pubkey, wallet: &Pubkey) -> Result<(), Failure> {
+        let payer = self.authority.insecure_clone();
+        let instruction = self.move_failure(game, wallet);
+        self.send_instruction(instruction, &[&payer]).map(|_| ())
+    }
+
+    /// Report the player's pending move as failed through the Arcium
+    /// program, as the cluster does. Needs `with_arcium_relay`.
+    pub fn fail_move(
+        &mut self,
+        game: &Pubkey,
+        wallet: &Pubkey,
+    ) -> Result<TransactionMetadata, Failure> {
+        let payer = self.authority.insecure_clone();
+        let instruction = relayed(self.move_failure(game, wallet));
+        self.send_instruction(instruction, &[&payer])
+    }
+
+    fn move_failure(&self, game: &Pubkey, wallet: &Pubkey) -> Instruction {
+        let offset = self
+            .player_account(game, wallet)
+            .pending_computation
+            .expect("a move is pending");
+        let arcium = self.arcium("move_player", offset);
+        program_instruction(
+            instruction::MoveCallback {
+                output: SignedComputationOutputs::Failure,
+            },
+            accounts::MoveCallback {
+                arcium_program: ARCIUM_PROG_ID,
+                comp_def_account: arcium.comp_def_account,
+                mxe_account: arcium.mxe_account,
+                computation_account: arcium.computation_account,
+                cluster_account: arcium.cluster_account,
+                instructions_sysvar: sysvar::instructions::ID,
+                player_account: player_pda(game, wallet),
+                game: *game,
+                encrypted_player_state: player_state_pda(game, wallet),
+            },
+        )
+    }
+
+    // =========================================================
+    // CLUSTER PLUMBING
+    // =========================================================
+
+    /// Install an MXE bound to a one-node cluster whose key we hold
+    fn install_cluster(&mut self) {
+        let mxe = MXEAccount {
+            cluster: Some(CLUSTER_OFFSET),
+            ..Default::default()
+        };
+        let cluster = Cluster {
+            nodes: vec![self.cluster.pubkey()],
+            ..Default::default()
+        };
+        self.set_anchor_account(&derive_mxe_pda!(), &mxe, ARCIUM_PROG_ID);
+        self.set_anchor_account(&cluster_pda(), &cluster, ARCIUM_PROG_ID);
+        // Fees are not modelled: an empty pool and a zeroed epoch clock
+        self.set_anchor_account(
+            &ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+            &FeePool::default(),
+            ARCIUM_PROG_ID,
+        );
+        self.set_anchor_account(
+            &ARCIUM_CLOCK_ACCOUNT_ADDRESS,
+            &ClockAccount::default(),
+            ARCIUM_PROG_ID,
+        );
+    }
+
+    fn init_comp_defs(&mut self) {
+        let payer = self.authority.insecure_clone();
+        // Every comp def context has the same accounts, so one struct serves all
+        let accounts = |encrypted_ix: &str| accounts::InitMapCompDef {
+            payer: payer.pubkey(),
+            mxe_account: derive_mxe_pda!(),
+            comp_def_account: derive_comp_def_pda!(comp_def_offset(encrypted_ix)),
+            arcium_program: ARCIUM_PROG_ID,
+            system_program: system_program::ID,
+        };
+        self.send(instruction::InitMapCompDef {}, accounts("init_map"), &[&payer]).unwrap();
+        self.send(instruction::InitBuriedLayerCompDef {}, accounts("init_buried_layer"), &[&payer])
+            .unwrap();
+        self.send(instruction::InitRegisterPlayerCompDef {}, accounts("register_player"), &[&payer])
+            .unwrap();
+        self.send(instruction::InitMoveCompDef {}, accounts("move_player"), &[&payer]).unwrap();
+        self.send(instruction::InitExploreCompDef {}, accounts("explore"), &[&payer]).unwrap();
+        self.send(instruction::InitBuryCompDef {}, accounts("bury"), &[&payer]).unwrap();
+        self.send(instruction::InitDigCompDef {}, accounts("dig"), &[&payer]).unwrap();
+        self.send(instruction::InitDescendCompDef {}, accounts("descend"), &[&payer]).unwrap();
+        self.send(instruction::InitUseItemCompDef {}, accounts("use_item"), &[&payer]).unwrap();
+        self.send(instruction::InitRevealStandingsCompDef {}, accounts("reveal_standings"), &[&payer])
+            .unwrap();
+    }
+
+    /// What init_comp_defs leaves behind, for the relay that doesn't create them
+    fn install_comp_defs(&mut self) {
+        for encrypted_ix in [
+            "init_map",
+            "init_buried_layer",
+            "register_player",
+            "move_player",
+            "explore",
+            "bury",
+            "dig",
+            "descend",
+            "use_item",
+            "reveal_standings",
+        ] {
+            self.set_anchor_account(
+                &derive_comp_def_pda!(comp_def_offset(encrypted_ix)),
+                &ComputationDefinitionAccount::default(),
+                ARCIUM_PROG_ID,
+            );
+        }
+    }
+
+    fn arcium(&self, encrypted_ix: &str, computation_offset: u64) -> ArciumAccounts {
+        ArciumAccounts {
+            sign_pda_account: derive_sign_pda!(),
+            comp_def_account: derive_comp_def_pda!(comp_def_offset(encrypted_ix)),
+            mxe_account: derive_mxe_pda!(),
+            mempool_account: derive_mempool_pda!(),
+            executing_pool: derive_execpool_pda!(),
+            computation_account: derive_comp_pda!(computation_offset),
+            cluster_account: cluster_pda(),
+        }
+    }
+
+    /// Sign an output the way the cluster does: over the computation and the output bytes
+    fn sign<T: AnchorSerialize>(&self, computation_offset: u64, output: T) -> SignedComputationOutputs<T> {
+        let signature = self
+            .cluster
+            .sign_message(&signed_bytes(computation_offset, &output));
+        SignedComputationOutputs::Success(output, signature.into())
+    }
+
+    /// Callbacks are the cluster's transactions; a rejected one is a bug in the program
+    fn callback(&mut self, ix: impl InstructionData, accounts: impl ToAccountMetas) {
+        if self.silent {
+            return;
+        }
+        let instruction = program_instruction(ix, accounts);
+        self.delivered.push(instruction.clone());
+        let payer = self.authority.insecure_clone();
+        self.send_instruction(instruction, &[&payer])
+            .expect("callback rejected a cluster-signed output");
+    }
+
+    fn try_callback(
+        &mut self,
+        ix: impl InstructionData,
+        accounts: impl ToAccountMetas,
+    ) -> Result<TransactionMetadata, Failure> {
+        let payer = self.authority.insecure_clone();
+        self.send(ix, accounts, &[&payer])
+    }
+
+    fn send(
+        &mut self,
+        ix: impl InstructionData,
+        accounts: impl ToAccountMetas,
+        signers: &[&Keypair],
+    ) -> Result<TransactionMetadata, Failure> {
+        self.send_instruction(program_instruction(ix, accounts), signers)
+    }
+
+    fn send_instruction(
+        &mut self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<TransactionMetadata, Failure> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&signers[0].pubkey()),
+            signers,
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx);
+        self.svm.expire_blockhash();
+        let meta = result.map_err(|failed| Failure::Rejected(failed.err))?;
+        self.landed.push(meta.clone());
+        Ok(meta)
+    }
+
+    fn offset(&mut self) -> u64 {
+        self.next_offset += 1;
+        self.next_offset
+    }
+
+    fn nonce(&mut self) -> u128 {
+        self.next_nonce += 1;
+        self.next_nonce
+    }
+
+    fn seal_mxe<const N: usize>(&mut self, fields: Vec<u128>) -> MXEEncryptedStruct<N> {
+        MXEEncryptedStruct {
+            nonce: self.nonce(),
+            ciphertexts: seal_all(fields),
+        }
+    }
+
+    fn seal_shared<const N: usize>(&mut self, player: &TestPlayer, fields: [u128; N]) -> SharedEncryptedStruct<N> {
+        SharedEncryptedStruct {
+            encryption_key: player.client_key,
+            nonce: self.nonce(),
+            ciphertexts: seal_all(fields.to_vec()),
+        }
+    }
+
+    fn fetch<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let account = self.svm.get_account(key).expect("account exists");
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Zero-copy accounts: discriminator then the raw struct
+    fn load<T: bytemuck::Pod>(&self, key: &Pubkey) -> T {
+        let account = self.svm.get_account(key).expect("account exists");
+        *bytemuck::from_bytes(&account.data[8..8 + std::mem::size_of::<T>()])
+    }
+
+    fn set_packed_account<T: Pack>(&mut self, key: &Pubkey, state: T) {
+        let mut data = vec![0u8; T::LEN];
+        T::pack(state, &mut data).unwrap();
+        let lamports = self.svm.minimum_balance_for_rent_exemption(data.len());
+        self.svm
+            .set_account(
+                *key,
+                solana_sdk::account::Account {
+                    lamports,
+                    data,
+                    owner: spl_token::ID,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+    }
+
+    fn set_anchor_account<T: AccountSerialize>(&mut self, key: &Pubkey, value: &T, owner: Pubkey) {
+        let mut data = Vec::new();
+        value.try_serialize(&mut data).unwrap();
+        let lamports = self.svm.minimum_balance_for_rent_exemption(data.len());
+        self.svm
+            .set_account(
+                *key,
+                solana_sdk::account::Account {
+                    lamports,
+                    data,
+                    owner,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+    }
+}
+
+fn program_instruction(ix: impl InstructionData, accounts: impl ToAccountMetas) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: ix.data(),
+    }
+}
+
+/// `instruction` invoked by the relay, so the Arcium program is the caller
+fn relayed(instruction: Instruction) -> Instruction {
+    let mut accounts = vec![AccountMeta::new_readonly(instruction.program_id, false)];
+    accounts.extend(instruction.accounts);
+    Instruction {
+        program_id: ARCIUM_PROG_ID,
+        accounts,
+        data: [RELAY_TAG.as_slice(), &instruction.data].concat(),
+    }
+}
+
+// =========================================================
+// ASSERTIONS & EVENTS
+// =========================================================
+
+/// The instruction failed with this program error
+pub fn assert_game_error<T: std::fmt::Debug>(result: Result<T, Failure>, error: GameError) {
+    let code = u32::from(error);
+    match result {
+        Err(Failure::Rejected(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(actual),
+        ))) if actual == code => {}
+        other => panic!("expected {code}, got {other:?}"),
+    }
+}
+
+/// Anchor events emitted in a transaction's logs
+pub fn events<E: Event + Discriminator>(meta: &TransactionMetadata) -> Vec<E> {
+    meta.logs
+        .iter()
+        .filter_map(|line| line.strip_prefix("Program data: "))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .filter(|data| data.starts_with(&E::DISCRIMINATOR))
+        .map(|data| E::try_from_slice(&data[8..]).unwrap())
+        .collect()
+}
+
+// =========================================================
+// PDAS
+// =========================================================
+
+pub fn game_pda(authority: &Pubkey, game_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"game", authority.as_ref(), &game_id.to_le_bytes()], &ID).0
+}
+
+pub fn player_pda(game: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"player", game.as_ref(), wallet.as_ref()], &ID).0
+}
+
+pub fn player_state_pda(game: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"player_state", game.as_ref(), wallet.as_ref()], &ID).0
+}
+
+pub fn visited_pda(game: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"visited", game.as_ref(), wallet.as_ref()], &ID).0
+}
+
+pub fn map_pda(game: &Pubkey, level: u8) -> Pubkey {
+    Pubkey::find_program_address(&[b"map", game.as_ref(), &[level]], &ID).0
+}
+
+pub fn buried_pda(game: &Pubkey, level: u8) -> Pubkey {
+    Pubkey::find_program_address(&[b"buried", game.as_ref(), &[level]], &ID).0
+}
+
+pub fn result_pda(game: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"result", game.as_ref()], &ID).0
+}
+
+pub fn vault_pda(game: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", game.as_ref()], &ID).0
+}
+
+fn cluster_pda() -> Pubkey {
+    Pubkey::find_program_address(&[CLUSTER_PDA_SEED, &CLUSTER_OFFSET.to_le_bytes()], &ARCIUM_PROG_ID).0
+}
+
+// =========================================================
+// IDENTITY CIPHER
+// =========================================================
+
+/// The 10x10 layout the original game shipped with
+pub fn classic_config() -> GameConfig {
+    GameConfig {
+        width: 10,
+        height: 10,
+        treasure_count: 15,
+        trap_count: 10,
+        treasure_min: 5,
+        treasure_max: 50,
+        trap_min: 5,
+        trap_max: 30,
+        levels: 1,
+        wall_count: 0,
+        spring_count: 0,
+        portal_pairs: 0,
+        item_count: 0,
+    }
+}
+
+pub fn free_economics() -> Economics {
+    Economics {
+        entry_fee_lamports: 0,
+        rake_bps: 0,
+        max_players: 0,
+        payout_curve: PayoutCurve::Proportional,
+    }
+}
+
+pub fn map_config(config: &GameConfig) -> sim::MapConfig {
+    sim::MapConfig {
+        width: config.width,
+        height: config.height,
+        treasure_count: config.treasure_count,
+        trap_count: config.trap_count,
+        treasure_min: config.treasure_min,
+        treasure_max: config.treasure_max,
+        trap_min: config.trap_min,
+        trap_max: config.trap_max,
+        levels: config.levels,
+        wall_count: config.wall_count,
+        spring_count: config.spring_count,
+        portal_pairs: config.portal_pairs,
+        item_count: config.item_count,
+    }
+}
+
+fn seal(value: u128) -> [u8; 32] {
+    let mut ciphertext = [0u8; 32];
+    ciphertext[..16].copy_from_slice(&value.to_le_bytes());
+    ciphertext
+}
+
+fn open(ciphertext: &[u8; 32]) -> u128 {
+    u128::from_le_bytes(ciphertext[..16].try_into().unwrap())
+}
+
+fn seal_all<const N: usize>(fields: Vec<u128>) -> [[u8; 32]; N] {
+    assert_eq!(fields.len(), N, "field count matches the circuit type");
+    let mut ciphertexts = [[0u8; 32]; N];
+    for (slot, value) in ciphertexts.iter_mut().zip(fields) {
+        *slot = seal(value);
+    }
+    ciphertexts
+}
+
+/// Client input, one ciphertext per field of the circuit's input struct
+fn seal_input<const N: usize>(fields: [u16; N]) -> [[u8; 32]; N] {
+    fields.map(|value| seal(value as u128))
+}
+
+fn signed_bytes<T: AnchorSerialize>(computation_offset: u64, output: &T) -> Vec<u8> {
+    let mut bytes = derive_comp_pda!(computation_offset).to_bytes().to_vec();
+    output.serialize(&mut bytes).unwrap();
+    bytes
+}
+
+fn map_fields(map: &sim::MapMatrix) -> Vec<u128> {
+    map.tiles
+        .iter()
+        .flat_map(|tile| [tile.tile_type as u128, tile.value as u128])
+        .collect()
+}
+
+fn map_from(ciphertexts: &[[u8; 32]]) -> sim::MapMatrix {
+    sim::MapMatrix {
+        tiles: std::array::from_fn(|i| sim::MapTile {
+            tile_type: open(&ciphertexts[i * 2]) as u8,
+            value: open(&ciphertexts[i * 2 + 1]) as u16,
+        }),
+    }
+}
+
+fn buried_fields(layer: &sim::BuriedLayer) -> Vec<u128> {
+    layer.loot.iter().map(|&loot| loot as u128).collect()
+}
+
+fn buried_from(ciphertexts: &[[u8; 32]]) -> sim::BuriedLayer {
+    sim::BuriedLayer {
+        loot: std::array::from_fn(|i| open(&ciphertexts[i]) as u16),
+    }
+}
+
+fn state_fields(state: &sim::PlayerState) -> Vec<u128> {
+    let mut fields = vec![
+        state.x as u128,
+        state.y as u128,
+        state.gold as u128,
+        state.health as u128,
+        state.level as u128,
+    ];
+    fields.extend(state.inventory.iter().map(|&count| count as u128));
+    fields
+}
+
+fn state_from(ciphertexts: &[[u8; 32]]) -> sim::PlayerState {
+    sim::PlayerState {
+        x: open(&ciphertexts[0]) as u8,
+        y: open(&ciphertexts[1]) as u8,
+        gold: open(&ciphertexts[2]) as u16,
+        health: open(&ciphertexts[3]) as u16,
+        level: open(&ciphertexts[4]) as u8,
+        inventory: std::array::from_fn(|i| open(&ciphertexts[5 + i]) as u8),
+    }
+}
+
+fn visited_fields(visited: &sim::VisitedTiles) -> Vec<u128> {
+    visited.visited.iter().map(|&v| v as u128).collect()
+}
+
+fn visited_from(ciphertexts: &[[u8; 32]]) -> sim::VisitedTiles {
+    sim::VisitedTiles {
+        visited: std::array::from_fn(|i| open(&ciphertexts[i]) != 0),
+    }
+}
@@ -0,0 +1,817 @@
+// ============================================================
+// BURIED TREASURE — PROGRAM TESTS
+// End-to-end runs against the mock MXE; every computation is
+// cross-checked against a shadow run of the plaintext simulator
+// ============================================================
+
+mod mock_mxe;
+#[path = "../mnt/user-data/outputs/buried-treasure/simulator/tests/common/mod.rs"]
+mod sim_common;
+
+use buried_treasure::{
+    instruction, ActionPerformed, ActionType, ComputationFailed, ComputationFinalized,
+    ComputationQueued, CooldownConfig, Economics, FailureReason, GameConfig, GameError, PayoutCurve,
+    PlayerDescended, TurnAdvanced,
+};
+use buried_treasure_simulator as sim;
+use mock_mxe::{
+    assert_game_error, classic_config, events, free_economics, map_config, Failure, GameOptions,
+    MockMxe,
+};
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Signer, transaction::TransactionError,
+};
+
+const SEED: u64 = 0x5eed;
+
+/// Game whose map puts a `tile_type` next to spawn
+fn game_with_neighbour(mxe: &mut MockMxe, config: GameConfig, tile_type: u8) -> (Pubkey, u64, (u8, u8)) {
+    let (seed, pos) =
+        sim_common::seed_with_spawn_neighbour(SEED..SEED + 1_000, &map_config(&config), tile_type)
+            .expect("no seed places the tile next to spawn");
+    let game = mxe.create_game(1, 0, config, seed).unwrap();
+    (game, seed, pos)
+}
+
+#[test]
+fn create_game_persists_encrypted_map() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let game = mxe.create_game(1, 0, classic_config(), SEED).unwrap();
+
+    let account = mxe.game(&game);
+    assert!(account.is_active);
+    assert_eq!(account.game_id, 1);
+    assert_eq!(account.treasures_remaining, 15);
+    assert_eq!(mxe.simulator(&game, None), sim::Game::new(SEED, map_config(&classic_config())));
+}
+
+#[test]
+fn create_game_rejects_unsupported_map_size() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let config = GameConfig {
+        width: 16,
+        height: 16,
+        ..classic_config()
+    };
+    assert_game_error(mxe.create_game(1, 0, config, SEED), GameError::UnsupportedMapSize);
+
+    let config = GameConfig {
+        treasure_count: 60,
+        trap_count: 40,
+        ..classic_config()
+    };
+    assert_game_error(mxe.create_game(2, 0, config, SEED), GameError::InvalidGameConfig);
+
+    // Without treasure, the game could never end as cleared
+    let config = GameConfig {
+        treasure_count: 0,
+        ..classic_config()
+    };
+    assert_game_error(mxe.create_game(3, 0, config, SEED), GameError::InvalidGameConfig);
+}
+
+#[test]
+fn full_game_flow_matches_simulator() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let (game, seed, (tx, ty)) = game_with_neighbour(&mut mxe, classic_config(), 1);
+    let alice = mxe.new_player();
+    let bob = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    mxe.register_player(&game, &bob).unwrap();
+
+    let mut shadow = sim::Game::new(seed, map_config(&classic_config()));
+    let a = shadow.register_player();
+    let b = shadow.register_player();
+
+    // Alice takes the treasure next to spawn
+    let outcome = mxe.explore(&game, &alice, tx, ty).unwrap();
+    assert_eq!(outcome, shadow.explore(a, tx, ty).unwrap());
+    assert!(outcome.treasure_claimed);
+
+    // Alice steps off spawn and buries part of her gold
+    assert_eq!(mxe.move_player(&game, &alice, 1, 1).unwrap(), shadow.move_player(a, 1, 1).unwrap());
+    let buried = mxe.bury(&game, &alice, 0, 0, 10).unwrap();
+    assert_eq!(buried, shadow.bury(a, 0, 0, 10).unwrap());
+    assert_eq!(buried.status, sim::STATUS_OK);
+
+    // Bob digs it up from (1, 0)
+    mxe.move_player(&game, &bob, 1, 0).unwrap();
+    shadow.move_player(b, 1, 0).unwrap();
+    let dug = mxe.dig(&game, &bob, 0, 0).unwrap();
+    assert_eq!(dug, shadow.dig(b, 0, 0).unwrap());
+    assert_eq!(dug.result.total_value, 10);
+
+    // Encrypted accounts hold exactly the simulator's state
+    let alice_view = mxe.simulator(&game, Some(&alice.wallet.pubkey()));
+    let bob_view = mxe.simulator(&game, Some(&bob.wallet.pubkey()));
+    assert_eq!(alice_view.maps, shadow.maps);
+    assert_eq!(alice_view.buried, shadow.buried);
+    assert_eq!(alice_view.players[0], shadow.players[a]);
+    assert_eq!(bob_view.players[0], shadow.players[b]);
+
+    // Public stats
+    let alice_account = mxe.player_account(&game, &alice.wallet.pubkey());
+    assert_eq!((alice_account.tiles_explored, alice_account.treasures_found), (1, 1));
+    let bob_account = mxe.player_account(&game, &bob.wallet.pubkey());
+    assert_eq!((bob_account.tiles_explored, bob_account.treasures_found), (1, 0));
+    assert_eq!(mxe.game(&game).treasures_remaining, 14);
+    assert_eq!(mxe.game(&game).player_count, 2);
+}
+
+#[test]
+fn descend_moves_player_to_the_next_level() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let config = GameConfig {
+        levels: 2,
+        ..classic_config()
+    };
+    let (game, seed, (sx, sy)) = game_with_neighbour(&mut mxe, config, 3);
+    assert_eq!(mxe.game(&game).treasures_remaining, 30);
+    assert_eq!(mxe.simulator(&game, None), sim::Game::new(seed, map_config(&config)));
+
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    let mut shadow = sim::Game::new(seed, map_config(&config));
+    let a = shadow.register_player();
+
+    // No stairs under the spawn
+    let outcome = mxe.descend(&game, &alice).unwrap();
+    assert_eq!(outcome, shadow.descend(a).unwrap());
+    assert_eq!(outcome.result.status, sim::STATUS_NO_STAIRS);
+    assert_eq!(mxe.player_account(&game, &alice.wallet.pubkey()).level, 0);
+
+    mxe.move_player(&game, &alice, sx, sy).unwrap();
+    shadow.move_player(a, sx, sy).unwrap();
+    let outcome = mxe.descend(&game, &alice).unwrap();
+    assert_eq!(outcome, shadow.descend(a).unwrap());
+    assert!(outcome.descended);
+    assert_eq!(outcome.result.level, 1);
+
+    assert_eq!(mxe.player_account(&game, &alice.wallet.pubkey()).level, 1);
+    let descended = &mxe.emitted::<PlayerDescended>()[0];
+    assert_eq!((descended.player, descended.level), (alice.wallet.pubkey(), 1));
+    assert_eq!(mxe.simulator(&game, Some(&alice.wallet.pubkey())).players[0], shadow.players[a]);
+
+    // Actions now run against the level-1 map
+    let explored = mxe.explore(&game, &alice, 1, 0).unwrap();
+    assert_eq!(explored, shadow.explore(a, 1, 0).unwrap());
+
+    assert_game_error(mxe.descend(&game, &alice), GameError::NoLevelBelow);
+}
+
+#[test]
+fn levels_count_once_their_map_lands() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let config = GameConfig {
+        levels: 2,
+        ..classic_config()
+    };
+    // Every init computation is queued, none lands
+    mxe.silent = true;
+    let game = mxe.create_game(1, 0, config, SEED).unwrap();
+    mxe.silent = false;
+    assert_eq!(mxe.game(&game).treasures_remaining, 0);
+
+    // No treasure was ever placed, so nobody can end the game as cleared
+    let bob = mxe.new_player();
+    assert_game_error(mxe.end_game(&game, &bob.wallet), GameError::GameStillRunning);
+
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    assert_game_error(mxe.descend(&game, &alice), GameError::LevelNotReady);
+    // Nor act on a level whose map never landed
+    assert_game_error(mxe.move_player(&game, &alice, 1, 0), GameError::LevelNotReady);
+    assert_game_error(mxe.dig(&game, &alice, 0, 0), GameError::LevelNotReady);
+}
+
+#[test]
+fn lost_level_init_is_retried() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let config = GameConfig {
+        levels: 2,
+        ..classic_config()
+    };
+    mxe.silent = true;
+    let game = mxe.create_game(1, 0, config, SEED).unwrap();
+    mxe.silent = false;
+
+    for level in 0..2 {
+        mxe.retry_level_init(&game, level, SEED).unwrap();
+        mxe.retry_buried_init(&game, level).unwrap();
+    }
+    let state = mxe.game(&game);
+    assert_eq!(state.levels_ready, 2);
+    assert_eq!(state.treasures_remaining, 2 * config.treasure_count as u32);
+    assert_game_error(mxe.retry_level_init(&game, 0, SEED), GameError::LevelAlreadyReady);
+    assert_game_error(mxe.retry_buried_init(&game, 1), GameError::LevelAlreadyReady);
+    assert_eq!(mxe.simulator(&game, None), sim::Game::new(SEED, map_config(&config)));
+
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    let outcome = mxe.descend(&game, &alice).unwrap();
+    assert_eq!(outcome.result.status, sim::STATUS_NO_STAIRS);
+}
+
+#[test]
+fn walls_and_portals_resolve_inside_move() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let config = GameConfig {
+        wall_count: 8,
+        spring_count: 4,
+        portal_pairs: 2,
+        ..classic_config()
+    };
+    let (game, seed, (wx, wy)) = game_with_neighbour(&mut mxe, config, 4);
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    let mut shadow = sim::Game::new(seed, map_config(&config));
+    let a = shadow.register_player();
+
+    let blocked = mxe.move_player(&game, &alice, wx, wy).unwrap();
+    assert_eq!(blocked, shadow.move_player(a, wx, wy).unwrap());
+    assert_eq!(blocked.status, sim::STATUS_BLOCKED);
+
+    // Walk onto whatever else is next to spawn; a portal moves her on
+    let (x, y) = [(1, 0), (0, 1), (1, 1)]
+        .into_iter()
+        .find(|&(x, y)| shadow.tile(x, y).tile_type != 4)
+        .unwrap();
+    assert_eq!(mxe.move_player(&game, &alice, x, y).unwrap(), shadow.move_player(a, x, y).unwrap());
+    assert_eq!(mxe.simulator(&game, Some(&alice.wallet.pubkey())).players[0], shadow.players[a]);
+
+    let config = GameConfig {
+        portal_pairs: 5,
+        ..classic_config()
+    };
+    assert_game_error(mxe.create_game(1_000, 0, config, SEED), GameError::InvalidGameConfig);
+}
+
+#[test]
+fn dug_up_items_can_be_used_once() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let config = GameConfig {
+        item_count: 10,
+        ..classic_config()
+    };
+    let (game, seed, (x, y)) = game_with_neighbour(&mut mxe, config, 7);
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    let mut shadow = sim::Game::new(seed, map_config(&config));
+    let a = shadow.register_player();
+
+    let dug = mxe.dig(&game, &alice, x, y).unwrap();
+    assert_eq!(dug, shadow.dig(a, x, y).unwrap());
+    let item = dug.result.item;
+    let view = mxe.simulator(&game, Some(&alice.wallet.pubkey()));
+    assert_eq!(view.players[0].state.inventory[item as usize], 1);
+
+    // Aim at the cache's old spot: a shovel finds no wall there and is kept
+    let used = mxe.use_item(&game, &alice, item, x, y).unwrap();
+    assert_eq!(used, shadow.use_item(a, item, x, y).unwrap());
+    let expected = if item == sim::ITEM_SHOVEL { sim::STATUS_NO_WALL } else { sim::STATUS_OK };
+    assert_eq!(used.status, expected);
+    assert_eq!(mxe.simulator(&game, Some(&alice.wallet.pubkey())).players[0], shadow.players[a]);
+
+    if item != sim::ITEM_SHOVEL {
+        let again = mxe.use_item(&game, &alice, item, x, y).unwrap();
+        assert_eq!(again.status, sim::STATUS_NO_ITEM);
+    }
+    let logged = mxe.emitted::<ActionPerformed>();
+    assert!(matches!(logged.last().unwrap().action_type, ActionType::UseItem));
+}
+
+#[test]
+fn register_twice_fails() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let game = mxe.create_game(1, 0, classic_config(), SEED).unwrap();
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+
+    // The player PDA already exists, so the system program refuses to create it
+    assert!(matches!(
+        mxe.register_player(&game, &alice),
+        Err(Failure::Rejected(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(0),
+        )))
+    ));
+}
+
+#[test]
+fn invalid_actions_are_rejected_privately() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let game = mxe.create_game(1, 0, classic_config(), SEED).unwrap();
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    let before = mxe.simulator(&game, Some(&alice.wallet.pubkey()));
+
+    // The circuit completes with a sealed status instead of aborting
+    let moved = mxe.move_player(&game, &alice, 3, 3).unwrap();
+    assert_eq!((moved.status, moved.new_x, moved.new_y), (sim::STATUS_NOT_ADJACENT, 0, 0));
+    let buried = mxe.bury(&game, &alice, 1, 0, 500).unwrap();
+    assert_eq!(buried.status, sim::STATUS_NOT_ENOUGH_GOLD);
+    assert_eq!(mxe.simulator(&game, Some(&alice.wallet.pubkey())), before);
+
+    // Publicly both look like any other finalized action
+    assert_eq!(mxe.emitted::<ComputationFinalized>().len(), 3);
+    assert!(mxe.emitted::<ComputationFailed>().is_empty());
+    assert!(mxe.player_account(&game, &alice.wallet.pubkey()).pending_computation.is_none());
+}
+
+#[test]
+fn lost_callback_is_released_by_abort_pending() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let game = mxe.create_game(1, 0, classic_config(), SEED).unwrap();
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+
+    mxe.silent = true;
+    mxe.move_player(&game, &alice, 1, 1).unwrap();
+    mxe.silent = false;
+
+    // The move stays pending until it times out
+    assert_game_error(mxe.bury(&game, &alice, 1, 0, 5), GameError::ComputationPending);
+    assert_game_error(
+        mxe.forge_move_failure(&game, &alice.wallet.pubkey()),
+        GameError::InvalidComputationOutput,
+    );
+    assert_game_error(
+        mxe.abort_pending(&game, &alice.wallet.pubkey()),
+        GameError::PendingNotExpired,
+    );
+    mxe.advance_clock(300);
+    // Released by the authority, not alice: the abort is permissionless
+    let meta = mxe.abort_pending(&game, &alice.wallet.pubkey()).unwrap();
+    let failed = &events::<ComputationFailed>(&meta)[0];
+    // After the level's two init computations and alice's registration
+    assert_eq!(failed.sequence, 4);
+    assert!(failed.reason == FailureReason::TimedOut);
+    assert!(mxe.player_account(&game, &alice.wallet.pubkey()).pending_computation.is_none());
+    assert_game_error(
+        mxe.abort_pending(&game, &alice.wallet.pubkey()),
+        GameError::NoPendingComputation,
+    );
+
+    // Alice never moved, so (1, 0) is still in reach
+    assert_eq!(mxe.bury(&game, &alice, 1, 0, 5).unwrap().status, sim::STATUS_OK);
+}
+
+#[test]
+fn cluster_failure_releases_pending_move() {
+    let Some(mut mxe) = MockMxe::with_arcium_relay() else { return };
+    let game = mxe.create_game(1, 0, classic_config(), SEED).unwrap();
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+
+    mxe.silent = true;
+    mxe.move_player(&game, &alice, 1, 1).unwrap();
+    mxe.silent = false;
+    let offset = mxe.player_account(&game, &alice.wallet.pubkey()).pending_computation.unwrap();
+
+    // Delivered by the Arcium program, the report is trusted without a signature
+    let meta = mxe.fail_move(&game, &alice.wallet.pubkey()).unwrap();
+    let failed = &events::<ComputationFailed>(&meta)[0];
+    assert_eq!((failed.computation_offset, failed.sequence), (offset, 4));
+    assert!(failed.reason == FailureReason::Aborted);
+    assert!(mxe.player_account(&game, &alice.wallet.pubkey()).pending_computation.is_none());
+    assert_eq!(mxe.bury(&game, &alice, 1, 0, 5).unwrap().status, sim::STATUS_OK);
+}
+
+#[test]
+fn second_registration_is_rejected() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let game = mxe.create_game(1, 0, classic_config(), SEED).unwrap();
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+
+    assert_game_error(mxe.register_player(&game, &alice), GameError::PlayerAlreadyRegistered);
+    assert_eq!(mxe.game(&game).player_count, 1);
+}
+
+#[test]
+fn failed_registration_is_retried() {
+    let Some(mut mxe) = MockMxe::with_arcium_relay() else { return };
+    let game = mxe.create_game(1, 0, classic_config(), SEED).unwrap();
+    let alice = mxe.new_player();
+
+    let meta = mxe.fail_registration(&game, &alice).unwrap();
+    let failed = &events::<ComputationFailed>(&meta)[0];
+    assert_eq!(failed.sequence, 3);
+    assert!(failed.reason == FailureReason::Aborted);
+    assert!(mxe.player_account(&game, &alice.wallet.pubkey()).pending_computation.is_none());
+
+    // The seat is kept, but there is no player state to act on yet
+    assert_eq!(mxe.game(&game).player_count, 1);
+    assert_game_error(mxe.move_player(&game, &alice, 1, 0), GameError::PlayerNotReady);
+
+    mxe.retry_registration(&game, &alice).unwrap();
+    assert_eq!(mxe.bury(&game, &alice, 1, 0, 5).unwrap().status, sim::STATUS_OK);
+    assert_game_error(
+        mxe.retry_registration(&game, &alice),
+        GameError::PlayerAlreadyRegistered,
+    );
+}
+
+#[test]
+fn actions_after_end_game_fail() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let game = mxe.create_game(1, 0, classic_config(), SEED).unwrap();
+    let alice = mxe.new_player();
+    let bob = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+
+    let authority = mxe.authority.insecure_clone();
+    mxe.end_game(&game, &authority).unwrap();
+    assert!(!mxe.game(&game).is_active);
+
+    assert_game_error(mxe.register_player(&game, &bob), GameError::GameNotActive);
+    assert_game_error(mxe.move_player(&game, &alice, 1, 1), GameError::GameNotActive);
+    assert_game_error(mxe.explore(&game, &alice, 1, 1), GameError::GameNotActive);
+    assert_game_error(mxe.bury(&game, &alice, 1, 1, 1), GameError::GameNotActive);
+    assert_game_error(mxe.dig(&game, &alice, 1, 1), GameError::GameNotActive);
+    assert_game_error(mxe.end_game(&game, &authority), GameError::GameNotActive);
+}
+
+#[test]
+fn deadline_closes_the_game() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let game = mxe.create_game(1, 60, classic_config(), SEED).unwrap();
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+
+    // Only the authority may end a running game
+    assert_game_error(mxe.end_game(&game, &alice.wallet), GameError::GameStillRunning);
+
+    mxe.advance_clock(60);
+    assert_game_error(mxe.explore(&game, &alice, 1, 1), GameError::GameNotActive);
+    mxe.end_game(&game, &alice.wallet).unwrap();
+}
+
+#[test]
+fn eliminated_player_cannot_act() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let config = GameConfig {
+        trap_min: 100,
+        trap_max: 100,
+        ..classic_config()
+    };
+    let (game, _, (x, y)) = game_with_neighbour(&mut mxe, config, 2);
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+
+    assert!(mxe.explore(&game, &alice, x, y).unwrap().eliminated);
+    let account = mxe.player_account(&game, &alice.wallet.pubkey());
+    assert!(account.eliminated);
+    assert_eq!(account.traps_triggered, 1);
+
+    assert_game_error(mxe.move_player(&game, &alice, 1, 1), GameError::PlayerEliminated);
+    assert_game_error(mxe.dig(&game, &alice, 1, 1), GameError::PlayerEliminated);
+}
+
+#[test]
+fn forged_callback_is_rejected() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let game = mxe.create_game(1, 0, classic_config(), SEED).unwrap();
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    let before = mxe.simulator(&game, Some(&alice.wallet.pubkey()));
+
+    assert_game_error(
+        mxe.forge_explore_callback(&game, &alice.wallet.pubkey()),
+        GameError::InvalidComputationOutput,
+    );
+    assert_eq!(mxe.simulator(&game, Some(&alice.wallet.pubkey())), before);
+    assert_eq!(mxe.game(&game).treasures_remaining, 15);
+}
+
+#[test]
+fn replayed_callbacks_are_rejected() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let (game, _, (x, y)) = game_with_neighbour(&mut mxe, classic_config(), 1);
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    mxe.explore(&game, &alice, x, y).unwrap();
+    mxe.bury(&game, &alice, 1, 1, 5).unwrap();
+    let before = mxe.simulator(&game, Some(&alice.wallet.pubkey()));
+
+    // Replays would restore the claimed treasure and wipe the buried gold
+    for replayed in mxe.replay::<instruction::InitMapCallback>() {
+        assert_game_error(replayed, GameError::NoPendingComputation);
+    }
+    for replayed in mxe.replay::<instruction::InitBuriedLayerCallback>() {
+        assert_game_error(replayed, GameError::NoPendingComputation);
+    }
+    for replayed in mxe.replay::<instruction::ExploreCallback>() {
+        assert_game_error(replayed, GameError::NoPendingComputation);
+    }
+    assert_eq!(mxe.simulator(&game, Some(&alice.wallet.pubkey())), before);
+    assert_eq!(mxe.game(&game).treasures_remaining, 14);
+
+    // A replayed reveal would count Alice twice
+    let authority = mxe.authority.insecure_clone();
+    mxe.end_game(&game, &authority).unwrap();
+    mxe.reveal_standing(&game, &alice.wallet.pubkey()).unwrap();
+    for replayed in mxe.replay::<instruction::RevealStandingsCallback>() {
+        assert_game_error(replayed, GameError::NoPendingComputation);
+    }
+    let result = mxe.game_result(&game);
+    assert_eq!((result.revealed_count, result.standings.len()), (1, 1));
+}
+
+#[test]
+fn reveal_standings_picks_winner() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let (game, _, (x, y)) = game_with_neighbour(&mut mxe, classic_config(), 1);
+    let alice = mxe.new_player();
+    let bob = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    mxe.register_player(&game, &bob).unwrap();
+    let found = mxe.explore(&game, &bob, x, y).unwrap().result.value;
+
+    let authority = mxe.authority.insecure_clone();
+    mxe.end_game(&game, &authority).unwrap();
+    assert_eq!(mxe.reveal_standing(&game, &alice.wallet.pubkey()).unwrap(), 20);
+    assert_eq!(mxe.reveal_standing(&game, &bob.wallet.pubkey()).unwrap(), 20 + found);
+    assert_game_error(
+        mxe.reveal_standing(&game, &bob.wallet.pubkey()),
+        GameError::StandingAlreadyRevealed,
+    );
+
+    let result = mxe.game_result(&game);
+    assert_eq!(result.revealed_count, 2);
+    assert_eq!(result.winner, bob.wallet.pubkey());
+    assert_eq!(result.winning_gold, 20 + found);
+}
+
+#[test]
+fn lost_reveal_can_be_queued_again() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let game = mxe.create_game(1, 0, classic_config(), SEED).unwrap();
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    let authority = mxe.authority.insecure_clone();
+    mxe.end_game(&game, &authority).unwrap();
+
+    mxe.silent = true;
+    mxe.reveal_standing(&game, &alice.wallet.pubkey()).unwrap();
+    mxe.silent = false;
+    assert!(!mxe.player_account(&game, &alice.wallet.pubkey()).standing_revealed);
+    assert_game_error(mxe.claim_winnings(&game, &alice), GameError::StandingsPending);
+    assert_game_error(
+        mxe.reveal_standing(&game, &alice.wallet.pubkey()),
+        GameError::ComputationPending,
+    );
+
+    mxe.advance_clock(300);
+    mxe.abort_pending(&game, &alice.wallet.pubkey()).unwrap();
+    assert_eq!(mxe.reveal_standing(&game, &alice.wallet.pubkey()).unwrap(), 20);
+    assert!(mxe.player_account(&game, &alice.wallet.pubkey()).standing_revealed);
+    mxe.claim_winnings(&game, &alice).unwrap();
+}
+
+#[test]
+fn unregistered_player_is_revealed_as_zero() {
+    let Some(mut mxe) = MockMxe::with_arcium_relay() else { return };
+    let game = mxe.create_game(1, 0, classic_config(), SEED).unwrap();
+    let alice = mxe.new_player();
+    let bob = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    mxe.fail_registration(&game, &bob).unwrap();
+    let authority = mxe.authority.insecure_clone();
+    mxe.end_game(&game, &authority).unwrap();
+
+    assert_eq!(mxe.reveal_standing(&game, &alice.wallet.pubkey()).unwrap(), 20);
+    assert_game_error(mxe.claim_winnings(&game, &alice), GameError::StandingsPending);
+    assert_game_error(
+        mxe.reveal_standing(&game, &bob.wallet.pubkey()),
+        GameError::PlayerNotReady,
+    );
+    assert_game_error(
+        mxe.reveal_unregistered(&game, &alice.wallet.pubkey()),
+        GameError::PlayerAlreadyRegistered,
+    );
+
+    mxe.reveal_unregistered(&game, &bob.wallet.pubkey()).unwrap();
+    assert_game_error(
+        mxe.reveal_unregistered(&game, &bob.wallet.pubkey()),
+        GameError::StandingAlreadyRevealed,
+    );
+    let result = mxe.game_result(&game);
+    assert_eq!(result.revealed_count, 2);
+    assert_eq!(result.winner, alice.wallet.pubkey());
+    mxe.claim_winnings(&game, &alice).unwrap();
+}
+
+#[test]
+fn staked_game_pays_out_by_final_gold() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let (game, _, (x, y)) = game_with_neighbour(&mut mxe, classic_config(), 1);
+    let mint = mxe.create_mint();
+    mxe.init_vault(&game, &mint, 1_000).unwrap();
+
+    let alice = mxe.new_player();
+    let bob = mxe.new_player();
+    let broke = mxe.new_player();
+    mxe.fund_tokens(&alice.wallet.pubkey(), &mint, 1_000);
+    mxe.fund_tokens(&bob.wallet.pubkey(), &mint, 1_000);
+    mxe.register_player(&game, &alice).unwrap();
+    mxe.register_player(&game, &bob).unwrap();
+    assert!(mxe.register_player(&game, &broke).is_err(), "no tokens, no entry");
+    assert_eq!(mxe.token_balance(&mock_mxe::vault_pda(&game)), 2_000);
+
+    // Stakes are fixed once players have joined
+    let other = mxe.create_mint();
+    assert_game_error(mxe.init_vault(&game, &other, 5), GameError::InvalidStake);
+
+    let found = mxe.explore(&game, &bob, x, y).unwrap().result.value;
+    let authority = mxe.authority.insecure_clone();
+    mxe.end_game(&game, &authority).unwrap();
+    mxe.reveal_standing(&game, &alice.wallet.pubkey()).unwrap();
+    assert_game_error(mxe.claim_winnings(&game, &bob), GameError::StandingsPending);
+    mxe.reveal_standing(&game, &bob.wallet.pubkey()).unwrap();
+
+    let total = 20 + 20 + found as u64;
+    let alice_share = 2_000 * 20 / total;
+    assert_eq!(mxe.claim_winnings(&game, &alice).unwrap(), (0, alice_share));
+    // The last claim sweeps the rounding dust, so the vault ends empty
+    assert_eq!(mxe.claim_winnings(&game, &bob).unwrap(), (0, 2_000 - alice_share));
+    assert_eq!(mxe.token_balance(&mock_mxe::vault_pda(&game)), 0);
+    assert_game_error(mxe.claim_winnings(&game, &bob), GameError::WinningsAlreadyClaimed);
+    assert_eq!(mxe.game_result(&game).token_pool.total, 2_000);
+}
+
+#[test]
+fn economics_cap_players_and_charge_entry_fees() {
+    const FEE: u64 = 1_000_000_000;
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let economics = Economics {
+        entry_fee_lamports: FEE,
+        rake_bps: 500,
+        max_players: 2,
+        payout_curve: PayoutCurve::WinnerTakesAll,
+    };
+    let options = GameOptions {
+        economics,
+        ..GameOptions::new(classic_config())
+    };
+    let game = mxe.create_game_with(1, options, SEED).unwrap();
+
+    let too_much = Economics {
+        rake_bps: 5_000,
+        ..free_economics()
+    };
+    let options = GameOptions {
+        economics: too_much,
+        ..GameOptions::new(classic_config())
+    };
+    assert_game_error(mxe.create_game_with(2, options, SEED), GameError::InvalidEconomics);
+
+    let poor = mxe.new_player_with_lamports(FEE / 2);
+    assert_game_error(mxe.register_player(&game, &poor), GameError::Underpayment);
+
+    let alice = mxe.new_player();
+    let bob = mxe.new_player();
+    let carol = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    mxe.register_player(&game, &bob).unwrap();
+    assert_game_error(mxe.register_player(&game, &carol), GameError::GameFull);
+
+    let authority = mxe.authority.insecure_clone();
+    mxe.end_game(&game, &authority).unwrap();
+    assert_game_error(mxe.withdraw_rake(&game, &alice.wallet), GameError::Unauthorized);
+
+    // Equal gold: the earlier-revealed player wins
+    mxe.reveal_standing(&game, &alice.wallet.pubkey()).unwrap();
+    mxe.reveal_standing(&game, &bob.wallet.pubkey()).unwrap();
+
+    let rake = 2 * FEE * 500 / 10_000;
+    assert_eq!(mxe.claim_winnings(&game, &alice).unwrap(), (2 * FEE - rake, 0));
+    assert_eq!(mxe.claim_winnings(&game, &bob).unwrap(), (0, 0));
+    assert_eq!(mxe.withdraw_rake(&game, &authority).unwrap(), (rake, 0));
+    assert_game_error(mxe.withdraw_rake(&game, &authority), GameError::RakeAlreadyWithdrawn);
+}
+
+#[test]
+fn turn_mode_enforces_move_order() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let options = GameOptions {
+        turn_duration: 30,
+        ..GameOptions::new(classic_config())
+    };
+    let game = mxe.create_game_with(1, options, SEED).unwrap();
+    let alice = mxe.new_player();
+    let bob = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    mxe.register_player(&game, &bob).unwrap();
+
+    assert_game_error(mxe.move_player(&game, &bob, 1, 1), GameError::NotYourTurn);
+    mxe.move_player(&game, &alice, 1, 1).unwrap();
+    assert_game_error(mxe.explore(&game, &alice, 2, 2), GameError::NotYourTurn);
+    assert_eq!(mxe.game(&game).turn_seat, 1);
+
+    // Bob stalls; once his turn times out anyone can pass it on
+    assert_game_error(mxe.skip_turn(&game, &bob.wallet.pubkey()), GameError::TurnNotExpired);
+    mxe.advance_clock(30);
+    let meta = mxe.skip_turn(&game, &bob.wallet.pubkey()).unwrap();
+    let advanced = &events::<TurnAdvanced>(&meta)[0];
+    assert_eq!(advanced.seat, 0);
+    assert!(advanced.skipped);
+
+    mxe.move_player(&game, &alice, 2, 2).unwrap();
+}
+
+#[test]
+fn create_game_bounds_durations() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let options = GameOptions {
+        turn_duration: i64::MAX,
+        ..GameOptions::new(classic_config())
+    };
+    assert_game_error(mxe.create_game_with(1, options, SEED), GameError::InvalidDuration);
+    let options = GameOptions {
+        duration: i64::MAX,
+        ..GameOptions::new(classic_config())
+    };
+    assert_game_error(mxe.create_game_with(2, options, SEED), GameError::InvalidDuration);
+}
+
+#[test]
+fn skip_turn_requires_turn_mode() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let game = mxe.create_game(1, 0, classic_config(), SEED).unwrap();
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+
+    assert_game_error(mxe.skip_turn(&game, &alice.wallet.pubkey()), GameError::TurnModeOff);
+}
+
+#[test]
+fn cooldown_limits_action_rate() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let options = GameOptions {
+        cooldown: CooldownConfig {
+            max_action_points: 2,
+            slots_per_point: 10,
+        },
+        ..GameOptions::new(classic_config())
+    };
+    let game = mxe.create_game_with(1, options, SEED).unwrap();
+    let alice = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    assert_eq!(mxe.player_account(&game, &alice.wallet.pubkey()).action_points, 2);
+
+    mxe.explore(&game, &alice, 1, 1).unwrap();
+    mxe.move_player(&game, &alice, 1, 1).unwrap();
+    assert_game_error(mxe.dig(&game, &alice, 2, 2), GameError::Cooldown);
+
+    // Points regenerate from the last action, one per `slots_per_point`
+    mxe.advance_slots(9);
+    assert_game_error(mxe.dig(&game, &alice, 2, 2), GameError::Cooldown);
+    mxe.advance_slots(1);
+    mxe.dig(&game, &alice, 2, 2).unwrap();
+
+    // The bucket never overfills
+    mxe.advance_slots(1_000);
+    mxe.explore(&game, &alice, 2, 1).unwrap();
+    assert_eq!(mxe.player_account(&game, &alice.wallet.pubkey()).action_points, 1);
+}
+
+#[test]
+fn actions_are_sequenced_and_paired_with_callbacks() {
+    let Some(mut mxe) = MockMxe::new() else { return };
+    let game = mxe.create_game(1, 0, classic_config(), SEED).unwrap();
+    let alice = mxe.new_player();
+    let bob = mxe.new_player();
+    mxe.register_player(&game, &alice).unwrap();
+    mxe.register_player(&game, &bob).unwrap();
+    mxe.move_player(&game, &alice, 1, 1).unwrap();
+    mxe.explore(&game, &bob, 1, 0).unwrap();
+    mxe.bury(&game, &alice, 2, 2, 5).unwrap();
+    mxe.dig(&game, &bob, 1, 1).unwrap();
+
+    let actions = mxe.emitted::<ActionPerformed>();
+    let kinds: Vec<_> = actions.iter().map(|action| action.action_type.clone() as u8).collect();
+    let expected = [
+        ActionType::Register,
+        ActionType::Register,
+        ActionType::Move,
+        ActionType::Explore,
+        ActionType::Bury,
+        ActionType::Dig,
+    ];
+    assert_eq!(kinds, expected.map(|kind| kind as u8));
+    let sequences: Vec<_> = actions.iter().map(|action| action.sequence).collect();
+    // init_map and init_buried_layer took sequences 1 and 2
+    assert_eq!(sequences, (3..=8).collect::<Vec<_>>());
+    assert_eq!(mxe.game(&game).action_sequence, 8);
+
+    // Every queued computation, level setup included, is finalized exactly
+    // once under the same offset
+    let queued = mxe.emitted::<ComputationQueued>();
+    let finalized = mxe.emitted::<ComputationFinalized>();
+    assert_eq!(queued.len(), 8);
+    assert_eq!(
+        queued.iter().map(|q| (q.computation_offset, q.sequence)).collect::<Vec<_>>(),
+        finalized.iter().map(|f| (f.computation_offset, f.sequence)).collect::<Vec<_>>(),
+    );
+}